@@ -0,0 +1,84 @@
+//! Compares `points::draw_all`, which batches markers by paint, against drawing the same points
+//! one at a time, on a large synthetic glyph (think CJK, or traced scans).
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use glifparser::glif::contour::MFEKContourCommon;
+use glifparser::{Glif, Handle, MFEKGlif, Point, PointType};
+use skia_safe::Surface;
+use std::collections::HashSet;
+
+use glifrenderer::points::kind::classify_in_contour;
+use glifrenderer::points::{draw_all, draw_complete_point, draw_handlebars, PointState};
+use glifrenderer::viewport::Viewport;
+
+/// `contours` circles of `points` smooth points each, laid out on a grid.
+fn synthetic_glyph(contours: usize, points: usize) -> MFEKGlif<()> {
+    let outline = (0..contours)
+        .map(|c| {
+            let (cx, cy) = ((c % 40) as f32 * 25., (c / 40) as f32 * 25.);
+            (0..points)
+                .map(|p| {
+                    let angle = p as f32 / points as f32 * std::f32::consts::TAU;
+                    let (x, y) = (cx + 10. * angle.cos(), cy + 10. * angle.sin());
+                    let (dx, dy) = (-angle.sin() * 2., angle.cos() * 2.);
+                    let mut point = Point::from_x_y_type((x, y), PointType::Curve);
+                    point.a = Handle::At(x + dx, y + dy);
+                    point.b = Handle::At(x - dx, y - dy);
+                    point
+                })
+                .collect()
+        })
+        .collect();
+    let mut glif = Glif::<()>::new();
+    glif.outline = Some(outline);
+    MFEKGlif::from(glif)
+}
+
+fn bench_points(c: &mut Criterion) {
+    let glyph = synthetic_glyph(400, 16);
+    let viewport = Viewport::default().with_winsize((1000., 1000.));
+    let mut surface = Surface::new_raster_n32_premul((1000, 1000)).unwrap();
+    let selected = HashSet::new();
+    let selected_handles = HashSet::new();
+
+    let mut group = c.benchmark_group("6400 points");
+    group.bench_function("draw_all (batched)", |b| {
+        b.iter(|| {
+            let canvas = surface.canvas();
+            draw_all(
+                &glyph,
+                &viewport,
+                0,
+                None,
+                None,
+                &selected,
+                &selected_handles,
+                None,
+                canvas,
+            );
+        })
+    });
+    group.bench_function("point by point", |b| {
+        b.iter(|| {
+            let canvas = surface.canvas();
+            let layer = &glyph.layers[0];
+            for contour in layer.outline.iter() {
+                let points: Vec<_> = contour.iter().collect();
+                for (pidx, point) in points.iter().copied().enumerate() {
+                    draw_handlebars(&viewport, point, (false, false), canvas);
+                    let state = PointState {
+                        kind: classify_in_contour(&points, pidx, contour.is_open()),
+                        selected: false,
+                        handles_selected: (false, false),
+                        tint: None,
+                    };
+                    draw_complete_point(&viewport, point, None, state, canvas);
+                }
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_points);
+criterion_main!(benches);
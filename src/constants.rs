@@ -19,17 +19,41 @@ pub static POINT_RADIUS: f32 = 3. * PEN_SIZE;
 pub static TRIANGLE_POINT_AREA: f32 = ((POINT_RADIUS + 1.) * 0.8) * PEN_SIZE;
 pub static HANDLE_RADIUS: f32 = 2.5 * PEN_SIZE;
 pub static HANDLEBAR_THICKNESS: f32 = 1. * PEN_SIZE;
+/// Length of the stub drawn instead of a handlebar in the floating handle style.
+pub static FLOATING_HANDLE_STUB: f32 = 6. * PEN_SIZE;
 pub static ANCHOR_RADIUS: f32 = 15. * PEN_SIZE;
 pub static ANCHOR_STROKE_THICKNESS: f32 = 3. * PEN_SIZE;
 pub static POINT_LABEL_PADDING: f32 = 3. * PEN_SIZE;
+/// Dash and dot intervals are in screen pixels, they don't grow or shrink with the zoom level.
+pub static DASH_ON_LENGTH: f32 = 6. * PEN_SIZE;
+pub static DASH_OFF_LENGTH: f32 = 4. * PEN_SIZE;
+pub static DOT_SPACING: f32 = 4. * PEN_SIZE;
+/// Minimum space between two guideline labels that had to be moved apart, in screen pixels.
+pub static GUIDELINE_LABEL_GAP: f32 = 2. * PEN_SIZE;
+pub static GUIDELINE_ANNOTATION_OFFSET: f32 = 5. * PEN_SIZE;
+pub static MARQUEE_STROKE_THICKNESS: f32 = 1. * PEN_SIZE;
+pub static CURVATURE_COMB_THICKNESS: f32 = 1. * PEN_SIZE;
+pub static WARNING_BADGE_SIZE: f32 = 5. * PEN_SIZE;
+/// How much bigger than a point's marker the halo of a hovered point or handle is.
+pub static HOVER_HALO_SCALE: f32 = 2.5;
+pub static HOVER_HALO_THICKNESS: f32 = 1. * PEN_SIZE;
+pub static GHOST_LINE_THICKNESS: f32 = 1. * PEN_SIZE;
+pub static GIZMO_STROKE_THICKNESS: f32 = 1. * PEN_SIZE;
+/// Half the side of a bounding box gizmo's scale handles, in screen pixels.
+pub static GIZMO_HANDLE_SIZE: f32 = 4. * PEN_SIZE;
+/// How far above the bounding box its rotation handle is, in screen pixels.
+pub static GIZMO_ROTATE_OFFSET: f32 = 20. * PEN_SIZE;
 
 /* Colors */
 pub static OUTLINE_FILL: u32 = 0xff_666666;
 pub static OUTLINE_STROKE: u32 = 0xff_000000;
 pub static POINT_SQUARE_FILL: u32 = 0xff_6ae755;
 pub static POINT_SQUARE_STROKE: u32 = 0xff_208e53;
+// Direction markers of counter-clockwise and open contours, then of clockwise ones.
 pub static DIRECTION_FILL: u32 = 0xff_a2e758;
 pub static DIRECTION_STROKE: u32 = 0xff_5c8e23;
+pub static DIRECTION_CW_FILL: u32 = 0xff_e7a258;
+pub static DIRECTION_CW_STROKE: u32 = 0xff_8e5c23;
 // One and two refers to number of Bezier handles.
 pub static POINT_ONE_FILL: u32 = 0xff_44cf8c;
 pub static POINT_ONE_STROKE: u32 = 0xff_1d8a84;
@@ -39,6 +63,8 @@ pub static HANDLE_FILL: u32 = 0xff_ff57ee;
 pub static HANDLE_STROKE: u32 = 0xff_999999;
 pub static HANDLEBAR_STROKE: u32 = 0xff_999999;
 pub static RIB_STROKE: u32 = 0xaa_ff7e7e;
+pub static VWS_PREVIEW_FILL: u32 = 0x33_000000;
+pub static PAP_PREVIEW_FILL: u32 = 0x33_000000;
 
 pub struct Style {
      
@@ -61,8 +87,26 @@ pub static SELECTED_HANDLE_FILL: u32 = 0xff_e6e6e6;
 pub static SELECTED_HANDLE_STROKE: u32 = 0xff_ffc829;
 pub static SELECTED_HANDLEBAR_STROKE: u32 = SELECTED_HANDLE_STROKE;
 
+pub static MARQUEE_FILL: u32 = 0x33_ffa115;
+pub static MARQUEE_STROKE: u32 = SELECTED_STROKE;
+
 pub static MEASURE_STROKE: u32 = 0xff_ff5050;
 
+pub static CURVATURE_COMB_STROKE: u32 = 0x88_5080ff;
+pub static CURVATURE_ENVELOPE_STROKE: u32 = 0xcc_2050cc;
+pub static CURVATURE_DISCONTINUITY_STROKE: u32 = 0xff_ff5050;
+pub static MISSING_EXTREMUM_STROKE: u32 = 0xff_ff5050;
+pub static INFLECTION_STROKE: u32 = 0xff_7d7d7d;
+pub static WARNING_BADGE_FILL: u32 = 0xff_ffcc00;
+pub static WARNING_BADGE_STROKE: u32 = 0xff_cc3300;
+pub static HOVER_HALO_FILL: u32 = 0x40_4fc3f7;
+pub static HOVER_HALO_STROKE: u32 = 0xff_0288d1;
+// The outline and points of a layer as it was before a drag, and lines to where they went.
+pub static GHOST_STROKE: u32 = 0x66_808080;
+pub static GHOST_LINE_STROKE: u32 = 0x99_0288d1;
+pub static GIZMO_STROKE: u32 = SELECTED_STROKE;
+pub static GIZMO_HANDLE_FILL: u32 = 0xff_ffffff;
+
 pub static GUIDELINE_STROKE: u32 = 0xff_7d7d7d;
 pub static LBEARING_STROKE: u32 = 0xff_000000;
 pub static RBEARING_STROKE: u32 = LBEARING_STROKE;
@@ -78,6 +122,12 @@ pub static PAPER_BGCOLOR: u32 = 0xff_ffffff;
 // be set here.
 pub static PAPER_FILL: u32 = 0xff_000000;
 
+// Contours are tinted with these in turn, by index.
+pub static CONTOUR_PALETTE: [u32; 8] = [
+    0xff_e6194b, 0xff_3cb44b, 0xff_4363d8, 0xff_f58231, 0xff_911eb4, 0xff_42d4f4, 0xff_f032e6,
+    0xff_9a6324,
+];
+
 pub static ANCHOR_FILL: u32 = 0xff_0000ff;
 pub static ANCHOR_STROKE: u32 = 0xff_000099;
 
@@ -94,3 +144,19 @@ pub static SELECTED_ANCHOR_COLOR: u32 = 0xff_00ffff;
 
 /* Math */
 pub const DEGREES_IN_RADIANS: f32 = PI / 180.0;
+/// Two handles less than this many degrees from a straight line make a smooth point.
+pub static SMOOTH_ANGLE_TOLERANCE: f32 = 1.;
+/// Curvature comb teeth are this many times the curvature long, by default.
+pub static CURVATURE_COMB_SCALE: f32 = 5000.;
+pub static CURVATURE_COMB_SAMPLES: usize = 32;
+pub static VWS_PREVIEW_SAMPLES: usize = 16;
+pub static PAP_PREVIEW_SAMPLES: usize = 16;
+/// Relative difference in curvature at a smooth point that is flagged as a discontinuity.
+pub static CURVATURE_DISCONTINUITY_TOLERANCE: f32 = 0.1;
+/// How far, in glyph units, an extremum may be from an on-curve point and still count as on it.
+pub static EXTREMUM_TOLERANCE: f32 = 0.5;
+/// On-curve points closer than this many glyph units are coincident.
+pub static COINCIDENT_POINT_TOLERANCE: f32 = 0.5;
+/// Maximum error, in glyph units, of the cubic to quadratic conversion assumed when numbering
+/// points in TrueType order.
+pub static TRUETYPE_CONVERSION_TOLERANCE: f32 = 1.;
@@ -1,8 +1,12 @@
 use super::constants::*;
 
+use crate::pap;
+use crate::points::contour_skia_path;
+use crate::points::names::contour_color;
 use crate::viewport::Viewport;
-use crate::{string::UiString, toggles::PreviewMode};
+use crate::{string::UiString, toggles::{ContourLabels, PreviewMode}};
 
+use glifparser::glif::Layer;
 use glifparser::outline::skia::ToSkiaPaths;
 use glifparser::{glif::LayerOperation, MFEKGlif, PointData};
 use skia_safe::{Path, Canvas, Color4f, Paint, PaintStyle, PathOp, Rect, Color};
@@ -102,7 +106,56 @@ pub fn draw_layer_group(
     }
 }
 
-//TODO: pub use crate::events::vws;
+/// Strokes each contour of `layer` in its own color from `CONTOUR_PALETTE`, over the outline drawn
+/// by `draw`, so contours can be told apart while debugging components and layer operations.
+pub fn draw_contour_tints<PD: PointData>(
+    viewport: &Viewport,
+    canvas: &mut Canvas,
+    layer: &Layer<PD>,
+) {
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    paint.set_style(PaintStyle::Stroke);
+    paint.set_stroke_width(OUTLINE_STROKE_THICKNESS * (1. / viewport.factor));
+    for (cidx, contour) in layer.outline.iter().enumerate() {
+        if let Some(path) = contour_skia_path(contour) {
+            paint.set_color(contour_color(cidx));
+            canvas.draw_path(&path, &paint);
+        }
+    }
+}
+
+/// Draws the result of each pattern-along-path (PAP) operation in `layer` as a translucent preview
+/// over its skeleton contour, then outlines every copy of the pattern in a color from
+/// `CONTOUR_PALETTE`, so their spacing and stretch can be seen.
+pub fn draw_pap_preview<PD: PointData>(
+    viewport: &Viewport,
+    canvas: &mut Canvas,
+    layer: &Layer<PD>,
+) {
+    if viewport.preview_mode == PreviewMode::Paper {
+        return;
+    }
+    let mut fill = Paint::default();
+    fill.set_anti_alias(true);
+    fill.set_style(PaintStyle::Fill);
+    fill.set_color(PAP_PREVIEW_FILL);
+    let mut stroke = Paint::default();
+    stroke.set_anti_alias(true);
+    stroke.set_style(PaintStyle::Stroke);
+    stroke.set_stroke_width(OUTLINE_STROKE_THICKNESS * (1. / viewport.factor));
+
+    for (_cidx, copies) in pap::layer_pattern_copies(layer) {
+        for copy in copies.iter() {
+            canvas.draw_path(copy, &fill);
+        }
+        for (i, copy) in copies.iter().enumerate() {
+            stroke.set_color(contour_color(i));
+            canvas.draw_path(copy, &stroke);
+        }
+    }
+}
+
 // Before we draw we've got to build a flattened path out of the glyph by resolving
 // each layer operation in turn.
 pub fn draw<PD: PointData>(canvas: &mut Canvas, glyph: &MFEKGlif<PD>, viewport: &Viewport, style: Option<Style>) {
@@ -225,4 +278,12 @@ pub fn draw<PD: PointData>(canvas: &mut Canvas, glyph: &MFEKGlif<PD>, viewport:
         &total_outline_path,
         &style
     );
+
+    if viewport.contour_labels == ContourLabels::IndicesAndColors
+        && viewport.preview_mode != PreviewMode::Paper
+    {
+        for layer in glyph.layers.iter().filter(|l| l.visible) {
+            draw_contour_tints(viewport, canvas, layer);
+        }
+    }
 }
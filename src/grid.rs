@@ -4,7 +4,7 @@ use skia_safe as skia;
 use MFEKmath::rect::FlipIfRequired as _;
 
 use crate::constants::{GRID_STROKE, GRID_THICKNESS};
-use crate::guidelines::draw_guideline_impl;
+use crate::guidelines::{draw_guideline_impl, LineStyle};
 use crate::viewport::Viewport;
 
 #[derive(Copy, Clone, Debug)]
@@ -17,6 +17,8 @@ pub struct Grid {
     pub slope: Option<f32>,
     /// whether to draw
     pub show: bool,
+    /// solid, dashed or dotted
+    pub style: LineStyle,
 }
 
 impl Default for Grid {
@@ -29,6 +31,7 @@ impl Default for Grid {
             spacing,
             slope,
             show: false,
+            style: LineStyle::Solid,
         }
     }
 }
@@ -56,6 +59,7 @@ pub fn draw(canvas: &mut Canvas, grid: &Grid, viewport: &Viewport) {
     paint.set_color(scolor);
     paint.set_stroke_width(GRID_THICKNESS * (1. / viewport.factor));
     paint.set_style(PaintStyle::Stroke);
+    grid.style.apply(viewport, &mut paint);
     // This works by making fake guidelines.
     let dmatrix = viewport.as_device_matrix();
     let mut sk_c_bounds = canvas.local_clip_bounds().unwrap();
@@ -81,7 +85,7 @@ pub fn draw(canvas: &mut Canvas, grid: &Grid, viewport: &Viewport) {
             grid.offset,
             Float(90.),
         );
-        draw_guideline_impl(viewport, canvas, &guideline, Some(GRID_STROKE), &mut path);
+        draw_guideline_impl(viewport, canvas, &guideline, Some(GRID_STROKE), &mut path, None);
     }
 
     // Draw horizontal guidelines (based on viewport location)
@@ -92,7 +96,7 @@ pub fn draw(canvas: &mut Canvas, grid: &Grid, viewport: &Viewport) {
             (i as f32).floor() + grid.offset,
             Float(0.),
         );
-        draw_guideline_impl(viewport, canvas, &guideline, Some(GRID_STROKE), &mut path);
+        draw_guideline_impl(viewport, canvas, &guideline, Some(GRID_STROKE), &mut path, None);
     }
 
     let total_horizontal =
@@ -115,7 +119,7 @@ pub fn draw(canvas: &mut Canvas, grid: &Grid, viewport: &Viewport) {
                 gslope * viewx + spacing * (i as f32).floor() + grid.offset,
                 Float(angle),
             );
-            draw_guideline_impl(viewport, canvas, &guideline, Some(GRID_STROKE), &mut path);
+            draw_guideline_impl(viewport, canvas, &guideline, Some(GRID_STROKE), &mut path, None);
         }
     }
 
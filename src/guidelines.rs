@@ -6,9 +6,154 @@ use super::constants::*;
 use flo_curves as flo;
 use glifparser::{Guideline, GuidelinePoint, IntegerOrFloat};
 use kurbo;
-use skia_safe::{self as skia, Canvas, Color, Paint, PaintStyle, Path};
+use skia_safe::{
+    self as skia, Canvas, Color, Matrix, Paint, PaintCap, PaintStyle, Path, PathEffect,
+};
 use MFEKmath::rect::FlipIfRequired;
 
+use std::cmp::Ordering;
+
+/// How a guideline (or the grid) is stroked. Intervals are given in screen pixels, so a dashed
+/// line looks the same at every zoom level.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LineStyle {
+    Solid,
+    Dashed { on: f32, off: f32 },
+    Dotted { spacing: f32 },
+}
+
+impl Default for LineStyle {
+    fn default() -> Self {
+        LineStyle::Solid
+    }
+}
+
+impl LineStyle {
+    pub fn dashed() -> Self {
+        LineStyle::Dashed {
+            on: DASH_ON_LENGTH,
+            off: DASH_OFF_LENGTH,
+        }
+    }
+
+    pub fn dotted() -> Self {
+        LineStyle::Dotted {
+            spacing: DOT_SPACING,
+        }
+    }
+
+    /// Sets up a stroking paint to draw in this style.
+    pub fn apply(&self, viewport: &Viewport, paint: &mut Paint) {
+        let factor = 1. / viewport.factor;
+        match *self {
+            LineStyle::Solid => {
+                paint.set_path_effect(None);
+            }
+            LineStyle::Dashed { on, off } => {
+                paint.set_stroke_cap(PaintCap::Butt);
+                paint.set_path_effect(PathEffect::dash(&[on * factor, off * factor], 0.));
+            }
+            LineStyle::Dotted { spacing } => {
+                // A zero length dash with a round cap is a dot as wide as the stroke.
+                paint.set_stroke_cap(PaintCap::Round);
+                paint.set_path_effect(PathEffect::dash(&[0., spacing * factor], 0.));
+            }
+        }
+    }
+}
+
+/// A guideline name, positioned but not yet drawn.
+struct PendingLabel {
+    name: String,
+    color: u32,
+    at: (f32, f32),
+    alignment: string::Alignment,
+    vcenter: string::VerticalAlignment,
+}
+
+impl PendingLabel {
+    fn uistring(&self) -> UiString<'_> {
+        UiString::with_colors(&self.name, self.color, None)
+            .autosized(string::AutoSizeMode::OnlySmaller)
+            .padding(1.)
+            .alignment(self.alignment)
+            .vcenter(self.vcenter)
+    }
+
+    fn device_rect(&self, viewport: &Viewport, matrix: &Matrix) -> skia::Rect {
+        let at = matrix.map_point(self.at);
+        let (width, height) = self.uistring().measure(viewport);
+        let left = match self.alignment {
+            string::Alignment::Left => at.x,
+            string::Alignment::Right => at.x - width,
+            string::Alignment::Center => at.x - width / 2.,
+        };
+        let top = match self.vcenter {
+            string::VerticalAlignment::Bottom => at.y - height,
+            string::VerticalAlignment::Top => at.y,
+        };
+        skia::Rect::from_xywh(left, top, width, height)
+    }
+
+    fn draw(&self, viewport: &Viewport, at: (f32, f32), canvas: &mut Canvas) {
+        self.uistring().draw(viewport, at, canvas);
+    }
+}
+
+fn rects_overlap(a: &skia::Rect, b: &skia::Rect) -> bool {
+    a.left < b.right && b.left < a.right && a.top < b.bottom && b.top < a.bottom
+}
+
+/// Collects the names of every guideline drawn in a frame, so that they can be laid out together
+/// instead of being drawn one on top of another when guidelines are close (x-height and its
+/// overshoot, for example). Pass it to [`draw_guideline_with_layout`], then call
+/// [`LabelLayout::draw`] once all guidelines are drawn.
+#[derive(Default)]
+pub struct LabelLayout {
+    labels: Vec<PendingLabel>,
+}
+
+impl LabelLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stacks colliding labels downwards on screen until none overlap, then draws and forgets
+    /// them.
+    pub fn draw(&mut self, viewport: &Viewport, canvas: &mut Canvas) {
+        let matrix = canvas.local_to_device_as_3x3();
+        let inverse = matrix.invert();
+        let labels = std::mem::take(&mut self.labels);
+
+        let mut rects: Vec<(usize, skia::Rect)> = labels
+            .iter()
+            .enumerate()
+            .map(|(i, l)| (i, l.device_rect(viewport, &matrix)))
+            .collect();
+        rects.sort_by(|(_, a), (_, b)| a.top.partial_cmp(&b.top).unwrap_or(Ordering::Equal));
+
+        let mut placed: Vec<skia::Rect> = vec![];
+        for (i, mut rect) in rects {
+            let original_top = rect.top;
+            while let Some(hit) = placed.iter().find(|p| rects_overlap(p, &rect)) {
+                rect.offset((0., hit.bottom - rect.top + GUIDELINE_LABEL_GAP));
+            }
+            placed.push(rect);
+
+            let label = &labels[i];
+            let at = match inverse {
+                Some(ref inverse) => {
+                    let at = matrix.map_point(label.at);
+                    let at = inverse.map_point((at.x, at.y + rect.top - original_top));
+                    (at.x, at.y)
+                }
+                None => label.at,
+            };
+            label.draw(viewport, at, canvas);
+        }
+    }
+}
+
 // This works by making four infinitely long lines at all edges of the viewport, then considering a
 // guideline, also of infinite length, finding where it intersects with all edges, and drawing it.
 pub(crate) fn draw_guideline_impl<PD: glifparser::PointData>(
@@ -17,6 +162,7 @@ pub(crate) fn draw_guideline_impl<PD: glifparser::PointData>(
     guideline: &Guideline<PD>,
     color: Option<u32>,
     path: &mut Path,
+    layout: Option<&mut LabelLayout>,
 ) {
     let factor = viewport.factor;
     let mut sk_c_bounds = canvas.local_clip_bounds().unwrap();
@@ -116,12 +262,17 @@ pub(crate) fn draw_guideline_impl<PD: glifparser::PointData>(
             at.x -= 5. * (1. / factor);
             string::Alignment::Right
         };
-        UiString::with_colors(name, color.unwrap_or(GUIDELINE_STROKE), None)
-            .autosized(string::AutoSizeMode::OnlySmaller)
-            .padding(1.)
-            .alignment(alignment)
-            .vcenter(vcenter)
-            .draw(&viewport, at.into(), canvas);
+        let label = PendingLabel {
+            name: name.clone(),
+            color: color.unwrap_or(GUIDELINE_STROKE),
+            at: at.into(),
+            alignment,
+            vcenter,
+        };
+        match layout {
+            Some(layout) => layout.labels.push(label),
+            None => label.draw(viewport, label.at, canvas),
+        }
     }
 }
 
@@ -130,9 +281,33 @@ pub fn draw_guideline<PD: glifparser::PointData>(
     canvas: &mut Canvas,
     guideline: &Guideline<PD>,
     color: Option<u32>,
+    style: LineStyle,
+) {
+    draw_guideline_maybe_layout(viewport, canvas, guideline, color, style, None);
+}
+
+/// Like [`draw_guideline`], but the guideline's name is queued in `layout` rather than drawn.
+pub fn draw_guideline_with_layout<PD: glifparser::PointData>(
+    viewport: &Viewport,
+    canvas: &mut Canvas,
+    guideline: &Guideline<PD>,
+    color: Option<u32>,
+    style: LineStyle,
+    layout: &mut LabelLayout,
+) {
+    draw_guideline_maybe_layout(viewport, canvas, guideline, color, style, Some(layout));
+}
+
+fn draw_guideline_maybe_layout<PD: glifparser::PointData>(
+    viewport: &Viewport,
+    canvas: &mut Canvas,
+    guideline: &Guideline<PD>,
+    color: Option<u32>,
+    style: LineStyle,
+    layout: Option<&mut LabelLayout>,
 ) {
     let mut path = Path::new();
-    draw_guideline_impl(viewport, canvas, guideline, color, &mut path);
+    draw_guideline_impl(viewport, canvas, guideline, color, &mut path, layout);
     let mut paint = Paint::default();
     paint.set_anti_alias(true);
     let color = color.unwrap_or(GUIDELINE_STROKE);
@@ -140,9 +315,45 @@ pub fn draw_guideline<PD: glifparser::PointData>(
     paint.set_color(scolor);
     paint.set_stroke_width(GUIDELINE_THICKNESS * (1. / viewport.factor));
     paint.set_style(PaintStyle::Stroke);
+    style.apply(viewport, &mut paint);
     canvas.draw_path(&path, &paint);
 }
 
+/// Draws a guideline's angle in degrees and its anchor position next to its anchor, e.g. for the
+/// selected guideline. Nothing is drawn while the anchor is off screen.
+pub fn draw_guideline_annotation<PD: glifparser::PointData>(
+    viewport: &Viewport,
+    canvas: &mut Canvas,
+    guideline: &Guideline<PD>,
+    color: Option<u32>,
+) {
+    let mut sk_c_bounds = canvas.local_clip_bounds().unwrap();
+    sk_c_bounds.flip_if_required();
+    let at = (guideline.at.x, guideline.at.y);
+    if at.0 < sk_c_bounds.left
+        || at.0 > sk_c_bounds.right
+        || at.1 < sk_c_bounds.top
+        || at.1 > sk_c_bounds.bottom
+    {
+        return;
+    }
+
+    let angle = f64::from(guideline.angle).rem_euclid(360.);
+    let angle = (angle * 10000.).round() / 10000.;
+    let round = |f: f32| (f * 100.).round() / 100.;
+    let s = format!("{}° ({}, {})", angle, round(at.0), round(at.1));
+
+    let offset = GUIDELINE_ANNOTATION_OFFSET * (1. / viewport.factor);
+    UiString::with_colors(
+        &s,
+        color.unwrap_or(GUIDELINE_STROKE),
+        Some(DEFAULT_STRING_BGCOLOR),
+    )
+    .autosized(string::AutoSizeMode::OnlySmaller)
+    .padding(1.)
+    .draw(viewport, (at.0 + offset, at.1 + offset), canvas);
+}
+
 /// Convenience function for drawing the baseline.
 pub fn draw_baseline<PD: glifparser::PointData>(viewport: &Viewport, canvas: &mut Canvas) {
     draw_guideline::<PD>(
@@ -150,5 +361,6 @@ pub fn draw_baseline<PD: glifparser::PointData>(viewport: &Viewport, canvas: &mu
         canvas,
         &Guideline::from_x_y_angle(0., 0., IntegerOrFloat::Float(0.)),
         Some(BASELINE_STROKE),
+        LineStyle::Solid,
     );
 }
@@ -8,7 +8,10 @@ pub mod constants;
 pub mod glyph;
 pub mod grid;
 pub mod guidelines;
+pub mod pap;
 pub mod points; // point drawing functions
+pub mod selection;
 pub mod string;
 pub mod toggles;
 pub mod viewport;
+pub mod vws;
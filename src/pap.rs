@@ -0,0 +1,148 @@
+//! Previews of MFEK's pattern-along-path (PAP) operation: copies of a pattern laid along a
+//! skeleton contour, each built on its own so they can be told apart.
+
+use glifparser::glif::contour::MFEKContourCommon;
+use glifparser::glif::contour_operations::pap::{PAPContour, PatternCopies, PatternStretch};
+use glifparser::glif::contour_operations::ContourOperations;
+use glifparser::glif::Layer;
+use glifparser::PointData;
+use kurbo::ParamCurve;
+use skia_safe::{ContourMeasure, ContourMeasureIter, Path, Point, Vector};
+
+use crate::constants::*;
+use crate::points::contour_skia_path;
+use crate::points::segments::cubic_segments;
+
+/// A pattern contour sampled into a polygon, in (along, across) coordinates: along the skeleton
+/// and away from it.
+struct Polyline {
+    points: Vec<(f64, f64)>,
+    closed: bool,
+}
+
+fn pattern_polylines<PD: PointData>(pap: &PAPContour<PD>) -> Vec<Polyline> {
+    pap.pattern
+        .iter()
+        .map(|contour| {
+            let mut points = vec![];
+            for seg in cubic_segments(contour) {
+                for i in 0..PAP_PREVIEW_SAMPLES {
+                    let p = seg.bez.eval(i as f64 / PAP_PREVIEW_SAMPLES as f64);
+                    // A vertical pattern runs along its y axis.
+                    points.push(if pap.is_vertical { (p.y, -p.x) } else { (p.x, p.y) });
+                }
+            }
+            if contour.is_open() {
+                if let Some(last) = cubic_segments(contour).last() {
+                    let p = last.bez.p3;
+                    points.push(if pap.is_vertical { (p.y, -p.x) } else { (p.x, p.y) });
+                }
+            }
+            Polyline {
+                points,
+                closed: !contour.is_open(),
+            }
+        })
+        .collect()
+}
+
+/// Position and unit normal at `distance` along the skeleton. Distances past the ends of an open
+/// skeleton carry on along its end tangents; a closed one wraps around.
+fn skeleton_at(cm: &ContourMeasure, distance: f32) -> Option<(Point, Vector)> {
+    let length = cm.length();
+    let (d, overshoot) = if cm.is_closed() {
+        (distance.rem_euclid(length), 0.)
+    } else {
+        let d = distance.clamp(0., length);
+        (d, distance - d)
+    };
+    let (pos, tan) = cm.pos_tan(d)?;
+    Some((pos + tan * overshoot, Vector::new(-tan.y, tan.x)))
+}
+
+/// Each copy of the pattern of `pap` laid along `skeleton`, with the operation's copies, spacing,
+/// stretch, scale and offsets. Overdraw isn't culled, so every copy is shown whole.
+fn lay_out<PD: PointData>(pap: &PAPContour<PD>, skeleton: &Path) -> Vec<Path> {
+    let cm = match ContourMeasureIter::from_path(skeleton, false, None).next() {
+        Some(cm) => cm,
+        None => return vec![],
+    };
+    let length = cm.length() as f64;
+    let pattern = pattern_polylines(pap);
+    let all = pattern.iter().flat_map(|p| p.points.iter());
+    let (min_along, max_along, min_across, max_across) = all.fold(
+        (f64::MAX, f64::MIN, f64::MAX, f64::MIN),
+        |(a0, a1, c0, c1), &(a, c)| (a0.min(a), a1.max(a), c0.min(c), c1.max(c)),
+    );
+    if min_along > max_along {
+        return vec![];
+    }
+    let (scale_along, scale_across) = pap.pattern_scale;
+    let width = (max_along - min_along) * scale_along;
+    let spacing = pap.spacing;
+    let closed = cm.is_closed();
+
+    // A closed skeleton has a gap after its last copy too.
+    let gaps = |n: f64| if closed { n } else { n - 1. };
+    let count = match pap.copies {
+        PatternCopies::Single => 1.,
+        // As many as fit, or one if copies don't take up any room.
+        PatternCopies::Repeated if width + spacing <= 0. => 1.,
+        PatternCopies::Repeated => {
+            let room = if closed { length } else { length + spacing };
+            (room / (width + spacing)).floor().max(1.)
+        }
+        PatternCopies::Fixed(n) => n as f64,
+    };
+    if count < 1. {
+        return vec![];
+    }
+    let (width, spacing) = match pap.stretch {
+        PatternStretch::Off => (width, spacing),
+        PatternStretch::On => ((length - gaps(count) * spacing) / count, spacing),
+        PatternStretch::Spacing if gaps(count) > 0. => {
+            (width, (length - count * width) / gaps(count))
+        }
+        PatternStretch::Spacing => (width, spacing),
+    };
+    let along_factor = if max_along > min_along { width / (max_along - min_along) } else { 0. };
+    let across_origin = if pap.center_pattern { (min_across + max_across) / 2. } else { 0. };
+
+    (0..count as usize)
+        .map(|copy| {
+            let start = pap.tangent_offset + copy as f64 * (width + spacing);
+            let mut path = Path::new();
+            for polyline in &pattern {
+                let points: Vec<Point> = polyline
+                    .points
+                    .iter()
+                    .filter_map(|&(a, c)| {
+                        let d = start + (a - min_along) * along_factor;
+                        let (pos, normal) = skeleton_at(&cm, d as f32)?;
+                        let across = (c - across_origin) * scale_across + pap.normal_offset;
+                        Some(pos + normal * across as f32)
+                    })
+                    .collect();
+                path.add_poly(&points, polyline.closed);
+            }
+            path
+        })
+        .collect()
+}
+
+/// The copies of the pattern of every pattern-along-path contour of `layer`, as (contour index,
+/// one path per copy). Skeletons and patterns that aren't cubic are converted to cubic first.
+pub fn layer_pattern_copies<PD: PointData>(layer: &Layer<PD>) -> Vec<(usize, Vec<Path>)> {
+    layer
+        .outline
+        .iter()
+        .enumerate()
+        .filter_map(|(cidx, contour)| match contour.operation() {
+            Some(ContourOperations::PatternAlongPath { data }) => {
+                let skeleton = contour_skia_path(contour)?;
+                Some((cidx, lay_out(data, &skeleton)))
+            }
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,104 @@
+//! Markers drawn by `draw_all` are accumulated into one path per paint, so that a glyph with
+//! thousands of points costs a handful of draw calls instead of several per point. Paths are kept
+//! in layers, so what's on top doesn't depend on which paint happened to be used first.
+
+use skia_safe::{Canvas, Paint, PaintStyle, Path as SkPath};
+
+#[derive(Clone, Copy, PartialEq)]
+struct BatchPaint {
+    color: u32,
+    style: PaintStyle,
+    width: f32,
+}
+
+/// The layers of a batch, bottom to top.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BatchLayer {
+    Halo,
+    Bars,
+    HandleFills,
+    Handles,
+    #[default]
+    Points,
+    /// Selected points and handles, over everything else.
+    Selected,
+}
+
+impl BatchLayer {
+    /// `Selected` if `selected`, else this layer.
+    pub fn or_selected(self, selected: bool) -> Self {
+        if selected {
+            BatchLayer::Selected
+        } else {
+            self
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct PointBatch {
+    layer: BatchLayer,
+    paths: Vec<(BatchLayer, BatchPaint, SkPath)>,
+}
+
+/// Replaces the alpha of an ARGB color, as `Paint::set_alpha_f` would.
+pub(crate) fn with_alpha(color: u32, alpha: f32) -> u32 {
+    (color & 0x00_ffffff) | (((alpha.clamp(0., 1.) * 255.).round() as u32) << 24)
+}
+
+impl PointBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the layer that paths from `stroke` and `fill` are in until it's set again.
+    pub fn set_layer(&mut self, layer: BatchLayer) -> &mut Self {
+        self.layer = layer;
+        self
+    }
+
+    fn path(&mut self, paint: BatchPaint) -> &mut SkPath {
+        let layer = self.layer;
+        let idx = match self.paths.iter().position(|(l, p, _)| *l == layer && *p == paint) {
+            Some(idx) => idx,
+            None => {
+                self.paths.push((layer, paint, SkPath::new()));
+                self.paths.len() - 1
+            }
+        };
+        &mut self.paths[idx].2
+    }
+
+    /// The path for everything stroked `width` wide in `color`.
+    pub fn stroke(&mut self, color: u32, width: f32) -> &mut SkPath {
+        self.path(BatchPaint {
+            color,
+            style: PaintStyle::Stroke,
+            width,
+        })
+    }
+
+    /// The path for everything filled with `color`.
+    pub fn fill(&mut self, color: u32) -> &mut SkPath {
+        self.path(BatchPaint {
+            color,
+            style: PaintStyle::Fill,
+            width: 0.,
+        })
+    }
+
+    /// Draws everything batched so far, layer by layer, and empties the batch. Within a layer
+    /// there's one call per paint, in the order each paint was first used.
+    pub fn draw(&mut self, canvas: &mut Canvas) {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        // Stable, so paints keep their order within a layer
+        self.paths.sort_by_key(|(layer, _, _)| *layer);
+        for (_layer, bp, path) in self.paths.drain(..) {
+            paint.set_style(bp.style);
+            paint.set_color(bp.color);
+            paint.set_stroke_width(bp.width);
+            canvas.draw_path(&path, &paint);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+//! Warnings for two on-curve points in the same place, and for handles of (near) zero length that
+//! aren't colocated with their point. Both are common production bugs that can't be seen by eye.
+
+use glifparser::glif::contour::MFEKContourCommon;
+use glifparser::glif::point::MFEKPointCommon;
+use glifparser::glif::Layer;
+use glifparser::{Handle as GPHandle, PointData as GPPointData, WhichHandle};
+use skia_safe::{Canvas, Paint, PaintStyle, Path as SkPath};
+use std::collections::HashMap;
+
+use super::hit::PointPart;
+use crate::constants::*;
+use crate::viewport::Viewport;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CoincidenceIssue {
+    /// Two on-curve points, as `(contour index, point index)`, at the same place.
+    CoincidentPoints {
+        first: (usize, usize),
+        second: (usize, usize),
+        at: (f32, f32),
+    },
+    /// A handle that's at its on-curve point without being colocated.
+    DegenerateHandle {
+        contour: usize,
+        point: usize,
+        part: PointPart,
+        at: (f32, f32),
+    },
+}
+
+impl CoincidenceIssue {
+    /// Where the issue's warning badge goes.
+    pub fn at(&self) -> (f32, f32) {
+        match self {
+            CoincidenceIssue::CoincidentPoints { at, .. } => *at,
+            CoincidenceIssue::DegenerateHandle { at, .. } => *at,
+        }
+    }
+}
+
+/// Finds the issues of `layer`, treating points less than `tolerance` units apart as coincident.
+/// `COINCIDENT_POINT_TOLERANCE` is a sane default.
+pub fn find_coincidences<PD: GPPointData>(
+    layer: &Layer<PD>,
+    tolerance: f32,
+) -> Vec<CoincidenceIssue> {
+    let mut ret = vec![];
+    let near = |a: (f32, f32), b: (f32, f32)| f32::hypot(a.0 - b.0, a.1 - b.1) <= tolerance;
+    // Points are bucketed into cells `tolerance` wide, so each is only compared with the points
+    // of its own and neighbouring cells.
+    let cell_size = tolerance.max(f32::EPSILON);
+    let cell = |at: (f32, f32)| {
+        (
+            (at.0 / cell_size).floor() as i64,
+            (at.1 / cell_size).floor() as i64,
+        )
+    };
+    let mut cells: HashMap<(i64, i64), Vec<((usize, usize), (f32, f32))>> = HashMap::new();
+
+    for (cidx, contour) in layer.outline.iter().enumerate() {
+        for (pidx, point) in contour.iter().enumerate() {
+            let at = (point.x(), point.y());
+
+            let (cx, cy) = cell(at);
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    for (other, other_at) in cells.get(&(cx + dx, cy + dy)).into_iter().flatten() {
+                        if near(at, *other_at) {
+                            ret.push(CoincidenceIssue::CoincidentPoints {
+                                first: *other,
+                                second: (cidx, pidx),
+                                at,
+                            });
+                        }
+                    }
+                }
+            }
+            cells.entry((cx, cy)).or_default().push(((cidx, pidx), at));
+
+            for (wh, part) in [
+                (WhichHandle::A, PointPart::HandleA),
+                (WhichHandle::B, PointPart::HandleB),
+            ] {
+                if let Some(GPHandle::At(x, y)) = point.get_handle(wh) {
+                    if near(at, (x, y)) {
+                        ret.push(CoincidenceIssue::DegenerateHandle {
+                            contour: cidx,
+                            point: pidx,
+                            part,
+                            at,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    ret
+}
+
+/// Draws a warning badge by each issue of `layer`, returning the issues so they can be listed.
+pub fn draw_coincidences<PD: GPPointData>(
+    viewport: &Viewport,
+    layer: &Layer<PD>,
+    tolerance: f32,
+    canvas: &mut Canvas,
+) -> Vec<CoincidenceIssue> {
+    let issues = find_coincidences(layer, tolerance);
+    let factor = viewport.factor;
+    let size = WARNING_BADGE_SIZE * (1. / factor);
+    // The badge sits up and to the right, clear of the point's own marker.
+    let offset = super::point_radius(factor) * 1.5;
+
+    let mut path = SkPath::new();
+    for issue in &issues {
+        let (x, y) = issue.at();
+        let (x, y) = (x + offset, y + offset);
+        path.move_to((x, y + size));
+        path.line_to((x + size * 0.866, y - size / 2.));
+        path.line_to((x - size * 0.866, y - size / 2.));
+        path.close();
+    }
+
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    paint.set_style(PaintStyle::Fill);
+    paint.set_color(WARNING_BADGE_FILL);
+    canvas.draw_path(&path, &paint);
+    paint.set_style(PaintStyle::Stroke);
+    paint.set_stroke_width(DIRECTION_STROKE_THICKNESS * (1. / factor));
+    paint.set_color(WARNING_BADGE_STROKE);
+    canvas.draw_path(&path, &paint);
+
+    issues
+}
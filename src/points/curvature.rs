@@ -0,0 +1,120 @@
+//! Curvature combs, for judging the quality of curves.
+
+use glifparser::glif::Layer;
+use glifparser::PointData as GPPointData;
+use kurbo::{CubicBez, ParamCurve, ParamCurveDeriv, Vec2};
+use skia_safe::{Canvas, Paint, PaintStyle, Path as SkPath};
+
+use super::segments::layer_segments;
+use crate::constants::*;
+use crate::viewport::Viewport;
+
+/// Position, unit left normal and signed curvature at `t`, or None where the curve's derivative
+/// vanishes (e.g. at a colocated handle) and curvature is undefined.
+fn sample(bez: &CubicBez, t: f64) -> Option<(Vec2, Vec2, f64)> {
+    let d1 = bez.deriv();
+    let d2 = d1.deriv();
+    let v = d1.eval(t).to_vec2();
+    let a = d2.eval(t).to_vec2();
+    let speed = v.hypot();
+    if speed < 1e-9 {
+        return None;
+    }
+    let curvature = v.cross(a) / (speed * speed * speed);
+    let normal = Vec2::new(-v.y, v.x) / speed;
+    Some((bez.eval(t).to_vec2(), normal, curvature))
+}
+
+/// Curvature and unit tangent at a segment end, nudged off the end if its handle is colocated.
+fn end_sample(bez: &CubicBez, end: f64) -> Option<(Vec2, f64)> {
+    [0., 1e-3, 1e-2]
+        .iter()
+        .map(|nudge| if end == 0. { *nudge } else { 1. - nudge })
+        .find_map(|t| sample(bez, t))
+        .map(|(_, normal, curvature)| (Vec2::new(normal.y, -normal.x), curvature))
+}
+
+/// Draws a curvature comb over every segment of `layer`: teeth perpendicular to the curve, as long
+/// as the curvature times `scale` (`CURVATURE_COMB_SCALE` is a sane default), their tips joined
+/// into an envelope. Points where two segments meet smoothly but with different curvature are
+/// circled in a warning color.
+pub fn draw_curvature_combs<PD: GPPointData>(
+    viewport: &Viewport,
+    layer: &Layer<PD>,
+    scale: f32,
+    canvas: &mut Canvas,
+) {
+    let factor = viewport.factor;
+    let scale = scale as f64;
+    let mut teeth = SkPath::new();
+    let mut envelope = SkPath::new();
+    let mut discontinuities = vec![];
+
+    for (_cidx, open, segments) in layer_segments(layer) {
+        for seg in &segments {
+            let mut first = true;
+            for i in 0..=CURVATURE_COMB_SAMPLES {
+                let t = i as f64 / CURVATURE_COMB_SAMPLES as f64;
+                let (at, normal, curvature) = match sample(&seg.bez, t) {
+                    Some(s) => s,
+                    None => continue,
+                };
+                // Teeth stand on the outside of the curve, away from its center of curvature
+                let tip = at - normal * curvature * scale;
+                teeth.move_to((at.x as f32, at.y as f32));
+                teeth.line_to((tip.x as f32, tip.y as f32));
+                if first {
+                    envelope.move_to((tip.x as f32, tip.y as f32));
+                    first = false;
+                } else {
+                    envelope.line_to((tip.x as f32, tip.y as f32));
+                }
+            }
+        }
+
+        // Compare each segment's end with the start of the one after it.
+        let joins = if open {
+            segments.len().saturating_sub(1)
+        } else {
+            segments.len()
+        };
+        for i in 0..joins {
+            let (incoming, outgoing) = (&segments[i], &segments[(i + 1) % segments.len()]);
+            let (tan_in, k_in) = match end_sample(&incoming.bez, 1.) {
+                Some(s) => s,
+                None => continue,
+            };
+            let (tan_out, k_out) = match end_sample(&outgoing.bez, 0.) {
+                Some(s) => s,
+                None => continue,
+            };
+            let smooth = tan_in.dot(tan_out) > 0.
+                && tan_in.cross(tan_out).abs()
+                    <= (SMOOTH_ANGLE_TOLERANCE as f64).to_radians().sin();
+            let largest = f64::max(k_in.abs(), k_out.abs());
+            if smooth
+                && largest > f64::EPSILON
+                && (k_in - k_out).abs() > largest * CURVATURE_DISCONTINUITY_TOLERANCE as f64
+            {
+                let at = outgoing.bez.p0;
+                discontinuities.push((at.x as f32, at.y as f32));
+            }
+        }
+    }
+
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    paint.set_style(PaintStyle::Stroke);
+    paint.set_stroke_width(CURVATURE_COMB_THICKNESS * (1. / factor));
+    paint.set_color(CURVATURE_COMB_STROKE);
+    canvas.draw_path(&teeth, &paint);
+    paint.set_color(CURVATURE_ENVELOPE_STROKE);
+    canvas.draw_path(&envelope, &paint);
+
+    paint.set_color(CURVATURE_DISCONTINUITY_STROKE);
+    paint.set_stroke_width(DIRECTION_STROKE_THICKNESS * (1. / factor));
+    let radius = super::point_radius(factor) * 2.;
+    for at in discontinuities {
+        canvas.draw_circle(at, radius, &paint);
+    }
+}
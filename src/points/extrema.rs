@@ -0,0 +1,115 @@
+//! Extrema and inflection point markers. Every horizontal and vertical extremum of a curve is
+//! expected to have an on-curve point, and a missing one is a common font production error.
+
+use glifparser::glif::Layer;
+use glifparser::PointData as GPPointData;
+use kurbo::common::solve_quadratic;
+use kurbo::{CubicBez, ParamCurve};
+use skia_safe::Canvas;
+
+use super::segments::layer_segments;
+use super::{draw_diamond_point, draw_round_point, handle_radius, point_radius};
+use crate::constants::*;
+use crate::viewport::Viewport;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Axis {
+    /// Leftmost or rightmost, where the curve's tangent is vertical.
+    X,
+    /// Topmost or bottommost, where the curve's tangent is horizontal.
+    Y,
+}
+
+/// An extremum in the middle of a segment, where there should be an on-curve point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MissingExtremum {
+    pub contour: usize,
+    /// The on-curve point the segment starts at.
+    pub point: usize,
+    pub axis: Axis,
+    pub at: (f32, f32),
+}
+
+/// Parameters in (0, 1) where a segment is extreme along each axis.
+fn axis_extrema(bez: &CubicBez) -> Vec<(Axis, f64)> {
+    let d0 = bez.p1 - bez.p0;
+    let d1 = bez.p2 - bez.p1;
+    let d2 = bez.p3 - bez.p2;
+    let one_coord = |axis: Axis, d0: f64, d1: f64, d2: f64| {
+        solve_quadratic(d0, 2. * (d1 - d0), d0 - 2. * d1 + d2)
+            .into_iter()
+            .filter(|t| *t > 0. && *t < 1.)
+            .map(move |t| (axis, t))
+    };
+    one_coord(Axis::X, d0.x, d1.x, d2.x)
+        .chain(one_coord(Axis::Y, d0.y, d1.y, d2.y))
+        .collect()
+}
+
+/// Parameters in (0, 1) where a segment's curvature changes sign.
+fn inflections(bez: &CubicBez) -> Vec<f64> {
+    let (p0, p1, p2, p3) = (
+        bez.p0.to_vec2(),
+        bez.p1.to_vec2(),
+        bez.p2.to_vec2(),
+        bez.p3.to_vec2(),
+    );
+    // Power basis: B(t) = at³ + bt² + ct + p0; B'(t) × B''(t) = 0 reduces to a quadratic.
+    let a = -p0 + p1 * 3. - p2 * 3. + p3;
+    let b = p0 * 3. - p1 * 6. + p2 * 3.;
+    let c = (p1 - p0) * 3.;
+    solve_quadratic(2. * c.cross(b), 6. * c.cross(a), -6. * a.cross(b))
+        .into_iter()
+        .filter(|t| *t > 0. && *t < 1.)
+        .collect()
+}
+
+/// Lists the extrema of `layer`'s segments that aren't on an on-curve point. Extrema closer than
+/// `EXTREMUM_TOLERANCE` units to either end of their segment count as being on the point.
+pub fn find_missing_extrema<PD: GPPointData>(layer: &Layer<PD>) -> Vec<MissingExtremum> {
+    let mut ret = vec![];
+    for (cidx, _open, segments) in layer_segments(layer) {
+        for seg in segments {
+            for (axis, t) in axis_extrema(&seg.bez) {
+                let at = seg.bez.eval(t);
+                let tolerance = EXTREMUM_TOLERANCE as f64;
+                if at.distance(seg.bez.p0) <= tolerance || at.distance(seg.bez.p3) <= tolerance {
+                    continue;
+                }
+                ret.push(MissingExtremum {
+                    contour: cidx,
+                    point: seg.from,
+                    axis,
+                    at: (at.x as f32, at.y as f32),
+                });
+            }
+        }
+    }
+    ret
+}
+
+/// Marks missing extrema in a warning color and inflection points in a neutral one, returning
+/// the missing extrema so they can also be listed.
+pub fn draw_extrema<PD: GPPointData>(
+    viewport: &Viewport,
+    layer: &Layer<PD>,
+    canvas: &mut Canvas,
+) -> Vec<MissingExtremum> {
+    let factor = viewport.factor;
+    for (_cidx, _open, segments) in layer_segments(layer) {
+        for seg in segments {
+            for t in inflections(&seg.bez) {
+                let at = seg.bez.eval(t);
+                let at = (at.x as f32, at.y as f32);
+                draw_diamond_point(at, handle_radius(factor), INFLECTION_STROKE, canvas, factor);
+            }
+        }
+    }
+
+    let missing = find_missing_extrema(layer);
+    for extremum in &missing {
+        let radius = point_radius(factor);
+        draw_round_point(extremum.at, radius, MISSING_EXTREMUM_STROKE, 1., canvas, factor);
+    }
+    missing
+}
@@ -0,0 +1,91 @@
+//! A translucent copy of a layer as it was before a drag, drawn under the live layer so the
+//! original positions stay visible while points are being moved.
+
+use glifparser::glif::contour::MFEKContourCommon;
+use glifparser::glif::point::MFEKPointCommon;
+use glifparser::glif::Layer;
+use glifparser::outline::skia::ToSkiaPaths;
+use glifparser::PointData as GPPointData;
+use skia_safe::{Canvas, Paint, PaintStyle};
+use std::collections::HashSet;
+
+use super::batch::PointBatch;
+use super::kind::{classify_in_contour, PointKind};
+use super::{batch_diamond_point, batch_round_point, batch_square_point, names, point_radius};
+use crate::constants::*;
+use crate::viewport::Viewport;
+
+fn point_at<PD: GPPointData>(
+    layer: &Layer<PD>,
+    (cidx, pidx): (usize, usize),
+) -> Option<(f32, f32)> {
+    let point = layer.outline.get(cidx)?.iter().nth(pidx)?;
+    Some((point.x(), point.y()))
+}
+
+/// Draws `snapshot`, the layer as it was when a drag began, as a translucent ghost, then a line
+/// from where each point of `moved` was to where it is in `live`. The distance moved is labeled
+/// once if every point moved the same way, as they do in a plain drag, and at each point if not.
+///
+/// Call this before drawing the live layer so that the ghost is underneath it.
+pub fn draw_ghost<PD: GPPointData>(
+    viewport: &Viewport,
+    snapshot: &Layer<PD>,
+    live: &Layer<PD>,
+    moved: &HashSet<(usize, usize)>,
+    canvas: &mut Canvas,
+) {
+    let factor = viewport.factor;
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    paint.set_style(PaintStyle::Stroke);
+    paint.set_color(GHOST_STROKE);
+    paint.set_stroke_width(OUTLINE_STROKE_THICKNESS * (1. / factor));
+    let skpaths = snapshot.outline.to_skia_paths(None);
+    for path in skpaths.open.iter().chain(skpaths.closed.iter()) {
+        canvas.draw_path(path, &paint);
+    }
+
+    let mut batch = PointBatch::new();
+    let radius = point_radius(factor);
+    for contour in snapshot.outline.iter() {
+        let points: Vec<_> = contour.iter().collect();
+        for (pidx, point) in points.iter().enumerate() {
+            let at = (point.x(), point.y());
+            let b = &mut batch;
+            match classify_in_contour(&points, pidx, contour.is_open()) {
+                PointKind::Smooth => batch_round_point(b, at, radius, GHOST_STROKE, 1., factor),
+                PointKind::Cusp => batch_diamond_point(b, at, radius, GHOST_STROKE, factor),
+                PointKind::Tangent(_) | PointKind::Corner => {
+                    batch_square_point(b, at, radius * 1.25, GHOST_STROKE, factor)
+                }
+            }
+        }
+    }
+
+    let mut moves: Vec<_> = moved
+        .iter()
+        .filter_map(|&idx| Some((idx, point_at(snapshot, idx)?, point_at(live, idx)?)))
+        .collect();
+    moves.sort_by_key(|(idx, _, _)| *idx);
+    let lines = batch.stroke(GHOST_LINE_STROKE, GHOST_LINE_THICKNESS * (1. / factor));
+    for (_, from, to) in moves.iter() {
+        lines.move_to(*from);
+        lines.line_to(*to);
+    }
+    batch.draw(canvas);
+
+    let delta = |from: (f32, f32), to: (f32, f32)| (to.0 - from.0, to.1 - from.1);
+    let label = |(dx, dy): (f32, f32)| format!("Δ {}, {}", dx.round() as i32, dy.round() as i32);
+    match moves.first() {
+        None => {}
+        Some(&(_, from, to)) if moves.iter().all(|&(_, f, t)| delta(f, t) == delta(from, to)) => {
+            names::draw_point_str(viewport, to, &label(delta(from, to)), canvas);
+        }
+        Some(_) => {
+            for &(_, from, to) in moves.iter() {
+                names::draw_point_str(viewport, to, &label(delta(from, to)), canvas);
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! Finding which point or handle is under the mouse, using the same geometry `draw_all` draws
+//! with.
+
+use glifparser::glif::contour::MFEKContourCommon;
+use glifparser::glif::point::MFEKPointCommon;
+use glifparser::glif::Layer;
+use glifparser::{Handle as GPHandle, PointData as GPPointData, WhichHandle};
+use skia_safe::Point as SkPoint;
+use std::collections::HashSet;
+
+use super::batch::BatchLayer;
+use super::kind::{classify_in_contour, PointKind};
+use super::quad::{is_implied, is_quadratic};
+use super::spiro;
+use super::{handle_radius, is_shown, point_radius};
+use crate::constants::DIRECTION_STROKE_THICKNESS;
+use crate::toggles::HandleStyle;
+use crate::viewport::Viewport;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PointPart {
+    Point,
+    HandleA,
+    HandleB,
+}
+
+impl PointPart {
+    pub fn which_handle(&self) -> Option<WhichHandle> {
+        match self {
+            PointPart::Point => None,
+            PointPart::HandleA => Some(WhichHandle::A),
+            PointPart::HandleB => Some(WhichHandle::B),
+        }
+    }
+}
+
+fn hits_round(at: (f32, f32), radius: f32, pos: SkPoint) -> bool {
+    let (dx, dy) = (pos.x - at.0, pos.y - at.1);
+    dx * dx + dy * dy <= radius * radius
+}
+
+fn hits_square(at: (f32, f32), side: f32, pos: SkPoint) -> bool {
+    (pos.x - at.0).abs() <= side / 2. && (pos.y - at.1).abs() <= side / 2.
+}
+
+/// Returns the topmost point or handle of `layer` drawn under `position`, a device-space (window)
+/// coordinate, as `(contour index, point index, part)`. `selected` and `selected_handles` are the
+/// sets given to `draw_all`, with its `vcidx` and `vpidx` point added if there is one, as selected
+/// markers are drawn over the rest. Nothing is hit that `draw_all` wouldn't draw: handles when the
+/// viewport's handle style is `HandleStyle::None`, and what the viewport's preview mode hides.
+pub fn hit_test<PD: GPPointData>(
+    layer: &Layer<PD>,
+    viewport: &Viewport,
+    selected: &HashSet<(usize, usize)>,
+    selected_handles: &HashSet<(usize, usize, PointPart)>,
+    position: (f32, f32),
+) -> Option<(usize, usize, PointPart)> {
+    let factor = viewport.factor;
+    let pos = viewport.as_device_matrix().invert()?.map_point(position);
+    // Markers are stroked, so half the stroke is part of what's seen.
+    let stroke = DIRECTION_STROKE_THICKNESS * (1. / factor) / 2.;
+    let point_radius = point_radius(factor);
+    let handle_radius = handle_radius(factor) + stroke;
+
+    // Walk the layers of `draw_all` top down, and each one backwards through the points, so the
+    // first hit is the topmost.
+    for top in [BatchLayer::Selected, BatchLayer::Points, BatchLayer::Handles] {
+        for (cidx, contour) in layer.outline.iter().enumerate().rev() {
+            let points: Vec<_> = contour.iter().collect();
+            let quadratic = is_quadratic(&points);
+            let spiro_types: Option<Vec<_>> =
+                contour.spiro().map(|s| s.iter().map(|p| p.ty).collect());
+            for (pidx, point) in points.iter().copied().enumerate().rev() {
+                let point_selected = selected.contains(&(cidx, pidx));
+                let any_selected = point_selected
+                    || selected_handles.contains(&(cidx, pidx, PointPart::HandleA))
+                    || selected_handles.contains(&(cidx, pidx, PointPart::HandleB));
+                if !is_shown(viewport, any_selected) {
+                    continue;
+                }
+                if viewport.handle_style != HandleStyle::None {
+                    for (wh, part) in [
+                        (WhichHandle::B, PointPart::HandleB),
+                        (WhichHandle::A, PointPart::HandleA),
+                    ] {
+                        // A quadratic off-curve point is drawn, so hit, as its previous point's A.
+                        if quadratic && part == PointPart::HandleB {
+                            continue;
+                        }
+                        let handle_selected =
+                            point_selected || selected_handles.contains(&(cidx, pidx, part));
+                        if BatchLayer::Handles.or_selected(handle_selected) != top {
+                            continue;
+                        }
+                        // Colocated handles aren't drawn, so can't be hit
+                        if let Some(GPHandle::At(x, y)) = point.get_handle(wh) {
+                            if hits_round((x, y), handle_radius, pos) {
+                                return Some((cidx, pidx, part));
+                            }
+                        }
+                    }
+                }
+
+                if BatchLayer::Points.or_selected(point_selected) != top {
+                    continue;
+                }
+                let at = (point.x(), point.y());
+                // Spiro points are drawn by their type, the rest by their handles.
+                let square = match spiro_types.as_ref().and_then(|types| types.get(pidx)) {
+                    Some(ty) => spiro::is_square(*ty),
+                    None => matches!(
+                        classify_in_contour(&points, pidx, contour.is_open()),
+                        PointKind::Corner
+                    ),
+                };
+                // Implied quadratic points are drawn as small as a handle.
+                let hit = if quadratic && is_implied(point) {
+                    hits_round(at, handle_radius, pos)
+                } else if square {
+                    hits_square(at, point_radius * 1.25 + stroke * 2., pos)
+                } else {
+                    hits_round(at, point_radius + stroke, pos)
+                };
+                if hit {
+                    return Some((cidx, pidx, PointPart::Point));
+                }
+            }
+        }
+    }
+
+    None
+}
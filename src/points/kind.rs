@@ -0,0 +1,125 @@
+//! Classification of on-curve points by the geometry of their handles, which decides the shape
+//! their marker is drawn with.
+
+use glifparser::glif::point::MFEKPointCommon;
+use glifparser::{Handle as GPHandle, PointData as GPPointData, WhichHandle};
+use skia_safe::Vector;
+
+use crate::constants::SMOOTH_ANGLE_TOLERANCE;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PointKind {
+    /// No handles, or one handle not continuing the line on its other side. Drawn as a square.
+    Corner,
+    /// Two handles on one straight line through the point. Drawn as a circle.
+    Smooth,
+    /// One handle continuing the line on its other side. Drawn as a triangle pointing along the
+    /// tangent, which is in the direction of the contour.
+    Tangent(Vector),
+    /// Two handles at an angle to each other. Drawn as a diamond.
+    Cusp,
+}
+
+impl PointKind {
+    /// Smooth and cusp points, having both handles, share their colors.
+    pub fn has_two_handles(&self) -> bool {
+        matches!(self, PointKind::Smooth | PointKind::Cusp)
+    }
+}
+
+fn handle_vector<PD: GPPointData>(
+    point: &dyn MFEKPointCommon<PD>,
+    wh: WhichHandle,
+) -> Option<(f32, f32)> {
+    match point.get_handle(wh) {
+        Some(GPHandle::At(x, y)) if (x, y) != (point.x(), point.y()) => {
+            Some((x - point.x(), y - point.y()))
+        }
+        _ => None,
+    }
+}
+
+fn vector_to<PD: GPPointData>(
+    from: &dyn MFEKPointCommon<PD>,
+    to: &dyn MFEKPointCommon<PD>,
+) -> (f32, f32) {
+    (to.x() - from.x(), to.y() - from.y())
+}
+
+/// Whether `u` and `v` point in opposite directions along the same line.
+fn colinear_opposite(u: (f32, f32), v: (f32, f32)) -> bool {
+    let (lu, lv) = (f32::hypot(u.0, u.1), f32::hypot(v.0, v.1));
+    if lu == 0. || lv == 0. {
+        return false;
+    }
+    let cross = (u.0 * v.1 - u.1 * v.0) / (lu * lv);
+    let dot = (u.0 * v.0 + u.1 * v.1) / (lu * lv);
+    dot < 0. && cross.abs() <= SMOOTH_ANGLE_TOLERANCE.to_radians().sin()
+}
+
+/// Classifies `point`, given its neighbours on the contour if it has them. Handle A leads to the
+/// next point and handle B comes from the previous one.
+pub fn classify_point<PD: GPPointData>(
+    point: &dyn MFEKPointCommon<PD>,
+    prev: Option<&dyn MFEKPointCommon<PD>>,
+    next: Option<&dyn MFEKPointCommon<PD>>,
+) -> PointKind {
+    let a = handle_vector(point, WhichHandle::A);
+    let b = handle_vector(point, WhichHandle::B);
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            if colinear_opposite(a, b) {
+                PointKind::Smooth
+            } else {
+                PointKind::Cusp
+            }
+        }
+        (Some(a), None) => match prev {
+            Some(prev) if colinear_opposite(a, vector_to(point, prev)) => {
+                PointKind::Tangent(Vector::new(a.0, a.1))
+            }
+            _ => PointKind::Corner,
+        },
+        (None, Some(b)) => match next {
+            Some(next) if colinear_opposite(b, vector_to(point, next)) => {
+                PointKind::Tangent(Vector::new(-b.0, -b.1))
+            }
+            _ => PointKind::Corner,
+        },
+        (None, None) => PointKind::Corner,
+    }
+}
+
+/// Classifies the point at `pidx` of a contour's `points`, wrapping around closed contours.
+pub fn classify_in_contour<PD: GPPointData>(
+    points: &[&dyn MFEKPointCommon<PD>],
+    pidx: usize,
+    open: bool,
+) -> PointKind {
+    let (prev, next) = neighbours(points, pidx, open);
+    classify_point(points[pidx], prev, next)
+}
+
+/// The points before and after `points[pidx]`, wrapping around a closed contour.
+pub(crate) fn neighbours<'a, PD: GPPointData>(
+    points: &[&'a dyn MFEKPointCommon<PD>],
+    pidx: usize,
+    open: bool,
+) -> (Option<&'a dyn MFEKPointCommon<PD>>, Option<&'a dyn MFEKPointCommon<PD>>) {
+    let len = points.len();
+    let prev = if pidx > 0 {
+        Some(points[pidx - 1])
+    } else if !open && len > 1 {
+        Some(points[len - 1])
+    } else {
+        None
+    };
+    let next = if pidx + 1 < len {
+        Some(points[pidx + 1])
+    } else if !open && len > 1 {
+        Some(points[0])
+    } else {
+        None
+    };
+    (prev, next)
+}
@@ -3,15 +3,30 @@ use glifparser::glif::contour::MFEKContourCommon;
 use glifparser::glif::point::MFEKPointCommon;
 use glifparser::outline::skia::ToSkiaPath;
 use skia_safe::{
-    Canvas, ContourMeasureIter, Matrix, Paint, PaintStyle, Path as SkPath, Point as SkPoint,
+    Canvas, ContourMeasure, ContourMeasureIter, Matrix, Paint, PaintStyle, Path as SkPath,
+    Point as SkPoint,
     Rect as SkRect, Vector,
 };
 use std::collections::HashSet;
 
+pub mod batch;
+pub mod coincident;
+pub mod curvature;
+pub mod extrema;
+pub mod ghost;
+pub mod hit;
+pub mod kind;
 pub mod names;
+pub mod numbering;
+pub mod quad;
+pub(crate) mod segments;
+pub mod spiro;
 
 use super::constants::*;
-use crate::toggles::{HandleStyle, PointLabels};
+use crate::toggles::{ContourLabels, HandleStyle, PointLabels, PreviewMode};
+use batch::{with_alpha, BatchLayer, PointBatch};
+use hit::PointPart;
+use kind::{classify_in_contour, PointKind};
 use crate::viewport::Viewport;
 
 use glifparser::{
@@ -38,28 +53,103 @@ impl<PD: GPPointData> SkiaFromGlyph<PD> for SkPoint {
 }
 
 
+/// A contour of any kind as a Skia path. Contours that aren't cubic (hyperbezier, Spiro) are
+/// converted to cubic first.
+pub(crate) fn contour_skia_path<PD: GPPointData>(
+    contour: &dyn MFEKContourCommon<PD>,
+) -> Option<SkPath> {
+    match contour.cubic() {
+        Some(cubic) => cubic.to_skia_path(None),
+        None => contour.to_cubic().cubic().and_then(|cubic| cubic.to_skia_path(None)),
+    }
+}
+
+/// Signed area of a contour, sampled along its length. In glyph (y up) coordinates, positive is
+/// counter-clockwise.
+fn sampled_area(cm: &ContourMeasure) -> f32 {
+    const SAMPLES: usize = 64;
+    let length = cm.length();
+    let points: Vec<SkPoint> = (0..SAMPLES)
+        .filter_map(|i| cm.pos_tan(length * i as f32 / SAMPLES as f32))
+        .map(|(p, _)| p)
+        .collect();
+    let mut area = 0.;
+    for (i, p) in points.iter().enumerate() {
+        let q = points[(i + 1) % points.len()];
+        area += p.x * q.y - q.x * p.y;
+    }
+    area / 2.
+}
+
+fn get_direction_fill_and_stroke(cm: &ContourMeasure, selected: bool) -> (Color, Color) {
+    if selected {
+        get_fill_and_stroke(UIPointType::Direction, selected)
+    } else if cm.is_closed() && sampled_area(cm) < 0. {
+        (DIRECTION_CW_FILL, DIRECTION_CW_STROKE)
+    } else {
+        (DIRECTION_FILL, DIRECTION_STROKE)
+    }
+}
+
+/// Draws each contour's start marker, a triangle along its outgoing tangent just past the start
+/// point, colored by the contour's winding direction unless the contour has selected points. Open
+/// contours also get a cap past their end point. Under `PreviewMode::NoUnselectedPoints` only
+/// contours with selected points get markers.
 pub fn draw_directions<PD: GPPointData>(
     viewport: &Viewport,
     layer: &Layer<PD>,
     canvas: &mut Canvas,
     selected: &HashSet<(usize, usize)>,
-    only_selected: bool,
 ) {
     let selected: HashSet<usize> = selected.into_iter().map(|(ci, _pi)| *ci).collect();
+    // Leave room for the start point's own marker
+    let clearance = point_radius(viewport.factor) * 1.25;
     for (ci, c) in layer.outline.iter().enumerate() {
-        drop(c.cubic().unwrap().to_skia_path(None).as_ref().map(|p| {
+        if !is_shown(viewport, selected.contains(&ci)) {
+            continue;
+        }
+        drop(contour_skia_path(c).as_ref().map(|p| {
             let piter = ContourMeasureIter::from_path(p, false, None);
             for cm in piter {
-                // Get vector and tangent -4 Skia units along the contur
-                let (vec, tan) = cm.pos_tan(-4.).unwrap();
-                if !only_selected || (only_selected && selected.contains(&ci)) {
-                    draw_triangle_point(viewport, vec, tan, false, canvas);
+                let colors = get_direction_fill_and_stroke(&cm, selected.contains(&ci));
+                if let Some((start, mut tan)) = cm.pos_tan(0.) {
+                    tan.set_length(clearance);
+                    draw_triangle_point(viewport, start + tan, tan, colors, canvas);
+                }
+                if !cm.is_closed() {
+                    if let Some((end, mut tan)) = cm.pos_tan(cm.length()) {
+                        tan.set_length(clearance);
+                        draw_end_cap(viewport, end + tan, tan, colors, canvas);
+                    }
                 }
             }
         }));
     }
 }
 
+/// A bar across the end of an open contour, perpendicular to its incoming tangent.
+fn draw_end_cap(
+    viewport: &Viewport,
+    at: SkPoint,
+    along: Vector,
+    (_fill, stroke): (Color, Color),
+    canvas: &mut Canvas,
+) {
+    let factor = viewport.factor;
+    let mut paint = Paint::default();
+    paint.set_stroke_width(DIRECTION_STROKE_THICKNESS * (1. / factor));
+    paint.set_anti_alias(true);
+    paint.set_style(PaintStyle::Stroke);
+    paint.set_color(stroke);
+
+    let mut across = Vector::new(-along.y, along.x);
+    across.set_length(TRIANGLE_POINT_AREA * (1. / factor));
+    let mut path = SkPath::new();
+    path.move_to(at + across);
+    path.line_to(at - across);
+    canvas.draw_path(&path, &paint);
+}
+
 // For direction markers, not a "real" point So, we make three paths. `path` we return; `path2` is
 // 90 deg offset from `path1`, both of which are lines created by elongating Skia vectors. `path2`
 // is rotated at its center, such that they form an X. We elongate `path1` a bit so the final
@@ -69,10 +159,9 @@ fn draw_triangle_point(
     viewport: &Viewport,
     at: SkPoint,
     along: Vector,
-    selected: bool,
+    (fill, stroke): (Color, Color),
     canvas: &mut Canvas,
 ) {
-    let (fill, stroke) = get_fill_and_stroke(UIPointType::Direction, selected);
     let factor = viewport.factor;
     let mut paint = Paint::default();
     paint.set_stroke_width(DIRECTION_STROKE_THICKNESS * (1. / factor));
@@ -127,14 +216,22 @@ pub fn draw_round_point(
     canvas: &mut Canvas,
     factor: f32,
 ) {
-    let mut paint = Paint::default();
-    paint.set_stroke_width(DIRECTION_STROKE_THICKNESS * (1. / factor));
-    paint.set_anti_alias(true);
+    let mut batch = PointBatch::new();
+    batch_round_point(&mut batch, at, radius, stroke, alpha, factor);
+    batch.draw(canvas);
+}
 
-    paint.set_style(PaintStyle::Stroke);
-    paint.set_color(stroke);
-    paint.set_alpha_f(alpha);
-    canvas.draw_circle((at.0, at.1), radius, &paint);
+pub(crate) fn batch_round_point(
+    batch: &mut PointBatch,
+    at: (f32, f32),
+    radius: f32,
+    stroke: Color,
+    alpha: f32,
+    factor: f32,
+) {
+    batch
+        .stroke(with_alpha(stroke, alpha), DIRECTION_STROKE_THICKNESS * (1. / factor))
+        .add_circle(at, radius, None);
 }
 
 pub fn draw_square_point(
@@ -145,21 +242,91 @@ pub fn draw_square_point(
     canvas: &mut Canvas,
     factor: f32,
 ) {
-    let mut paint = Paint::default();
-    paint.set_stroke_width(DIRECTION_STROKE_THICKNESS * (1. / factor));
-    paint.set_anti_alias(true);
+    let mut batch = PointBatch::new();
+    batch_square_point(&mut batch, at, radius, stroke, factor);
+    batch.draw(canvas);
+}
 
-    let mut path = SkPath::new();
-    path.add_rect(
-        SkRect::from_point_and_size((at.0 - radius / 2., at.1 - radius / 2.), (radius, radius)),
-        None,
-    );
-    path.close();
-    paint.set_color(stroke);
-    paint.set_style(PaintStyle::Stroke);
-    canvas.draw_path(&path, &paint);
+pub(crate) fn batch_square_point(
+    batch: &mut PointBatch,
+    at: (f32, f32),
+    radius: f32,
+    stroke: Color,
+    factor: f32,
+) {
+    batch
+        .stroke(stroke, DIRECTION_STROKE_THICKNESS * (1. / factor))
+        .add_rect(
+            SkRect::from_point_and_size((at.0 - radius / 2., at.1 - radius / 2.), (radius, radius)),
+            None,
+        );
+}
+
+pub fn draw_diamond_point(
+    at: (f32, f32),
+    radius: f32,
+    stroke: Color,
+    canvas: &mut Canvas,
+    factor: f32,
+) {
+    let mut batch = PointBatch::new();
+    batch_diamond_point(&mut batch, at, radius, stroke, factor);
+    batch.draw(canvas);
+}
+
+pub(crate) fn batch_diamond_point(
+    batch: &mut PointBatch,
+    at: (f32, f32),
+    radius: f32,
+    stroke: Color,
+    factor: f32,
+) {
+    batch
+        .stroke(stroke, DIRECTION_STROKE_THICKNESS * (1. / factor))
+        .move_to((at.0, at.1 + radius))
+        .line_to((at.0 + radius, at.1))
+        .line_to((at.0, at.1 - radius))
+        .line_to((at.0 - radius, at.1))
+        .close();
+}
+
+/// An equilateral triangle around `at`, with a vertex pointing along `along`.
+pub fn draw_tangent_point(
+    at: (f32, f32),
+    radius: f32,
+    along: Vector,
+    stroke: Color,
+    canvas: &mut Canvas,
+    factor: f32,
+) {
+    let mut batch = PointBatch::new();
+    batch_tangent_point(&mut batch, at, radius, along, stroke, factor);
+    batch.draw(canvas);
 }
 
+fn batch_tangent_point(
+    batch: &mut PointBatch,
+    at: (f32, f32),
+    radius: f32,
+    along: Vector,
+    stroke: Color,
+    factor: f32,
+) {
+    let at = SkPoint::from(at);
+    let mut tip = along;
+    if !tip.set_length(radius) {
+        // zero length tangent, can't tell where to point
+        tip = Vector::new(0., radius);
+    }
+    let base = Vector::new(-tip.y, tip.x) * 0.866;
+
+    batch
+        .stroke(stroke, DIRECTION_STROKE_THICKNESS * (1. / factor))
+        .move_to(at + tip)
+        .line_to(at - tip * 0.5 + base)
+        .line_to(at - tip * 0.5 - base)
+        .close();
+}
 
 fn get_fill_and_stroke(kind: UIPointType, selected: bool) -> (Color, Color) {
     let (fill, stroke) = if selected {
@@ -204,50 +371,138 @@ pub fn get_point_stroke_fill(round: bool, selected: bool) -> (Color, Color) {
         },
     }
 }
+/// Radius of an on-curve point's marker in glyph units. Square (corner) points use
+/// `radius * 1.25` as the length of their side.
+pub(crate) fn point_radius(factor: f32) -> f32 {
+    POINT_RADIUS * (1. / factor) * 1.75
+}
+
+pub(crate) fn handle_radius(factor: f32) -> f32 {
+    POINT_RADIUS * (1. / factor)
+}
+
+/// Whether a point, handle or direction marker is drawn under the viewport's preview mode.
+/// `selected` is whether it, or for a direction marker any point of its contour, is selected.
+pub(crate) fn is_shown(viewport: &Viewport, selected: bool) -> bool {
+    match viewport.preview_mode {
+        PreviewMode::None => true,
+        PreviewMode::NoUnselectedPoints => selected,
+        PreviewMode::Paper => false,
+    }
+}
+
+/// How a point is drawn, apart from where: its marker's shape, whether it and its handles (A, B)
+/// are selected, and its contour's tint, if any.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PointState {
+    pub kind: PointKind,
+    pub selected: bool,
+    pub handles_selected: (bool, bool),
+    pub tint: Option<Color>,
+}
+
+fn get_point_colors(state: PointState) -> (Color, Color) {
+    // Selection shows through a contour's tint
+    match state.tint {
+        Some(tint) if !state.selected => (tint, tint),
+        _ => get_point_stroke_fill(state.kind.has_two_handles(), state.selected),
+    }
+}
+
 pub fn draw_point<PD: GPPointData>(
     viewport: &Viewport,
     point: &dyn MFEKPointCommon<PD>,
     number: Option<isize>,
-    selected: bool,
+    state: PointState,
     canvas: &mut Canvas,
+) {
+    let (a, b) = state.handles_selected;
+    if !is_shown(viewport, state.selected || a || b) {
+        return;
+    }
+    let mut batch = PointBatch::new();
+    batch_point(&mut batch, viewport, point, state);
+    batch.draw(canvas);
+    draw_point_labels(viewport, point, number, state, canvas);
+}
+
+fn batch_point<PD: GPPointData>(
+    batch: &mut PointBatch,
+    viewport: &Viewport,
+    point: &dyn MFEKPointCommon<PD>,
+    state: PointState,
+) {
+    batch_marker(batch, viewport, point, state);
+
+    if viewport.handle_style != HandleStyle::None {
+        if let Some(a) = point.get_handle(WhichHandle::A) {
+            batch_handle(batch, viewport, a, state.handles_selected.0);
+        }
+        if let Some(b) = point.get_handle(WhichHandle::B) {
+            batch_handle(batch, viewport, b, state.handles_selected.1);
+        }
+    }
+}
+
+/// The marker of an on-curve point, without its handles.
+pub(crate) fn batch_marker<PD: GPPointData>(
+    batch: &mut PointBatch,
+    viewport: &Viewport,
+    point: &dyn MFEKPointCommon<PD>,
+    state: PointState,
 ) {
     let factor = viewport.factor;
     let at = (point.x(), point.y());
-    let original = at;
-    let mut paint = Paint::default();
-    paint.set_anti_alias(true);
-    paint.set_style(PaintStyle::StrokeAndFill);
-    let thiccness = POINT_STROKE_THICKNESS;
-    paint.set_stroke_width(thiccness * (1. / factor));
-    let radius = POINT_RADIUS * (1. / factor) * 1.75;
-
-    let round = point.get_handle_position(WhichHandle::A).is_some() && point.get_handle_position(WhichHandle::B).is_some();
-    let (stroke, fill) = get_point_stroke_fill(round, selected);
-    if round {
-        draw_round_point(at, radius, fill, 1., canvas, factor);
-    } else {
-        draw_square_point(at, radius * 1.25, fill, stroke, canvas, factor);
+    let radius = point_radius(factor);
+
+    let (_stroke, fill) = get_point_colors(state);
+    batch.set_layer(BatchLayer::Points.or_selected(state.selected));
+    match state.kind {
+        PointKind::Smooth => batch_round_point(batch, at, radius, fill, 1., factor),
+        PointKind::Cusp => batch_diamond_point(batch, at, radius, fill, factor),
+        PointKind::Tangent(along) => batch_tangent_point(batch, at, radius, along, fill, factor),
+        PointKind::Corner => batch_square_point(batch, at, radius * 1.25, fill, factor),
     }
+}
 
-    match number {
-        None => {}
-        Some(i) => match viewport.point_labels {
-            PointLabels::None => {}
-            PointLabels::Numbered => names::draw_point_number(viewport, at, i, canvas),
-            PointLabels::Locations => names::draw_point_location(viewport, at, original, canvas),
-        },
-    }
+/// Labels can't be batched like markers, so they're drawn separately, after them.
+fn draw_point_labels<PD: GPPointData>(
+    viewport: &Viewport,
+    point: &dyn MFEKPointCommon<PD>,
+    number: Option<isize>,
+    state: PointState,
+    canvas: &mut Canvas,
+) {
+    let at = (point.x(), point.y());
+
+    // Points without a number just go without a number label.
+    let labels: Vec<String> = viewport
+        .point_labels
+        .iter()
+        .filter_map(|label| match label {
+            PointLabels::Numbered => number.map(|i| i.to_string()),
+            PointLabels::Locations => Some(names::location_str(at)),
+            PointLabels::Types => Some(names::point_type_str(point.get_point_type()).into()),
+            PointLabels::Smooth => point.get_smooth().filter(|s| *s).map(|_| "smooth".into()),
+            PointLabels::Identifiers => point.get_identifier().map(|id| id.to_string()),
+        })
+        .collect();
+    names::draw_point_strs(viewport, at, &labels, canvas);
 
     if let Some(name) = point.get_name().as_ref() {
+        let (stroke, _fill) = get_point_colors(state);
         names::draw_point_name(viewport, at, stroke, name, canvas);
     }
 
     if viewport.handle_style != HandleStyle::None {
-        if let Some(a) = point.get_handle(WhichHandle::A) {
-            draw_handle::<PD>(viewport, a, selected, canvas);
-        }
-        if let Some(b) = point.get_handle(WhichHandle::B) {
-            draw_handle::<PD>(viewport, b, selected, canvas);
+        for (wh, selected) in [
+            (WhichHandle::A, state.handles_selected.0),
+            (WhichHandle::B, state.handles_selected.1),
+        ] {
+            // Only selected handles are labelled, there'd be too many labels otherwise
+            if let (Some(GPHandle::At(x, y)), true) = (point.get_handle(wh), selected) {
+                names::draw_handle_label(viewport, at, (x, y), canvas);
+            }
         }
     }
 }
@@ -258,74 +513,167 @@ pub fn get_handle_stroke_fill(selected: bool) -> (Color, Color) {
         false => (HANDLE_FILL, HANDLE_STROKE),
     }
 }
-fn draw_handle<PD: GPPointData>(
+
+pub(crate) fn batch_handle(
+    batch: &mut PointBatch,
     viewport: &Viewport,
     h: GPHandle,
     selected: bool,
-    canvas: &mut Canvas,
 ) {
     // if the handle is colocated there is nothing to draw
     if let GPHandle::At(x, y) = h {
         let at = (x, y);
-        let mut paint = Paint::default();
-        paint.set_anti_alias(true);
-        paint.set_style(PaintStyle::StrokeAndFill);
-        paint.set_stroke_width(HANDLE_STROKE_THICKNESS * (1. / viewport.factor));
-        let radius = POINT_RADIUS * (1. / viewport.factor);
-        let (_fill, stroke) = get_handle_stroke_fill(selected);
-        draw_round_point(at, radius, stroke, 0.5, canvas, viewport.factor);
+        let radius = handle_radius(viewport.factor);
+        let (fill, stroke) = get_handle_stroke_fill(selected);
+        // Without a bar tying it to its point, a floating handle is a solid marker
+        if viewport.handle_style == HandleStyle::Floating {
+            batch.set_layer(BatchLayer::HandleFills.or_selected(selected));
+            batch.fill(fill).add_circle(at, radius, None);
+        }
+        batch.set_layer(BatchLayer::Handles.or_selected(selected));
+        batch_round_point(batch, at, radius, stroke, 0.5, viewport.factor);
     }
 }
 
+/// Draws the bars joining a point to its handles. Each bar is colored by whether its handle (A,
+/// B) is selected.
 pub fn draw_handlebars<PD: GPPointData>(
     viewport: &Viewport,
     point: &dyn MFEKPointCommon<PD>,
-    selected: bool,
+    handles_selected: (bool, bool),
     canvas: &mut Canvas,
 ) {
-    let mut path = SkPath::new();
-    let mut paint = Paint::default();
-
-    paint.set_anti_alias(true);
-    paint.set_color(if selected {
-        SELECTED_HANDLEBAR_STROKE
-    } else {
-        HANDLEBAR_STROKE
-    });
-    paint.set_stroke_width(HANDLEBAR_THICKNESS * (1. / viewport.factor));
-    paint.set_style(PaintStyle::Stroke);
+    if !is_shown(viewport, handles_selected.0 || handles_selected.1) {
+        return;
+    }
+    let mut batch = PointBatch::new();
+    batch_handlebars(&mut batch, viewport, point, handles_selected);
+    batch.draw(canvas);
+}
 
-    paint.set_alpha_f(0.5);
-    if let Some((x, y)) = point.get_handle_position(WhichHandle::A) {
-        path.move_to((x, y));
-        path.line_to((point.x(), point.y()));
-    } else {
-        path.move_to((point.x(), point.y()));
+fn batch_handlebars<PD: GPPointData>(
+    batch: &mut PointBatch,
+    viewport: &Viewport,
+    point: &dyn MFEKPointCommon<PD>,
+    handles_selected: (bool, bool),
+) {
+    let width = HANDLEBAR_THICKNESS * (1. / viewport.factor);
+    batch.set_layer(BatchLayer::Bars);
+    for (wh, selected) in [
+        (WhichHandle::A, handles_selected.0),
+        (WhichHandle::B, handles_selected.1),
+    ] {
+        if let Some((x, y)) = point.get_handle_position(wh) {
+            let color = if selected {
+                SELECTED_HANDLEBAR_STROKE
+            } else {
+                HANDLEBAR_STROKE
+            };
+            batch
+                .stroke(with_alpha(color, 0.5), width)
+                .move_to((point.x(), point.y()))
+                .line_to((x, y));
+        }
     }
+}
 
-    if let Some((x, y)) = point.get_handle_position(WhichHandle::B) {
-        path.line_to((x, y));
+/// The floating counterpart to [`draw_handlebars`]: instead of a bar reaching the point, each
+/// handle gets a short stub pointing back along the tangent towards its on-curve point.
+pub fn draw_handle_stubs<PD: GPPointData>(
+    viewport: &Viewport,
+    point: &dyn MFEKPointCommon<PD>,
+    handles_selected: (bool, bool),
+    canvas: &mut Canvas,
+) {
+    if !is_shown(viewport, handles_selected.0 || handles_selected.1) {
+        return;
     }
+    let mut batch = PointBatch::new();
+    batch_handle_stubs(&mut batch, viewport, point, handles_selected);
+    batch.draw(canvas);
+}
 
-    canvas.draw_path(&path, &paint);
+fn batch_handle_stubs<PD: GPPointData>(
+    batch: &mut PointBatch,
+    viewport: &Viewport,
+    point: &dyn MFEKPointCommon<PD>,
+    handles_selected: (bool, bool),
+) {
+    let factor = viewport.factor;
+    let width = HANDLEBAR_THICKNESS * (1. / factor);
+
+    let on_curve = SkPoint::new(point.x(), point.y());
+    batch.set_layer(BatchLayer::Bars);
+    for (wh, selected) in [
+        (WhichHandle::A, handles_selected.0),
+        (WhichHandle::B, handles_selected.1),
+    ] {
+        if let Some(GPHandle::At(x, y)) = point.get_handle(wh) {
+            let at = SkPoint::new(x, y);
+            let mut along = on_curve - at;
+            let length = along.length();
+            // Short handles get a shorter stub so it never reaches the point itself
+            if !along.set_length(f32::min(FLOATING_HANDLE_STUB * (1. / factor), length / 2.)) {
+                continue;
+            }
+            let mut gap = along;
+            gap.set_length(handle_radius(factor));
+            let color = if selected {
+                SELECTED_HANDLEBAR_STROKE
+            } else {
+                HANDLEBAR_STROKE
+            };
+            batch
+                .stroke(color, width)
+                .move_to(at + gap)
+                .line_to(at + gap + along);
+        }
+    }
 }
 
 pub fn draw_complete_point<PD: GPPointData>(
     viewport: &Viewport,
     point: &dyn MFEKPointCommon<PD>,
     number: Option<isize>,
-    selected: bool,
+    state: PointState,
     canvas: &mut Canvas,
 ) {
-    draw_point(
-        viewport,
-        point,
-        number,
-        selected,
-        canvas,
-    );
+    draw_point(viewport, point, number, state, canvas);
+}
+
+/// Where a hovered point or handle is, if `hovered` names one that exists and, for a handle, isn't
+/// colocated with its point.
+fn hovered_position<PD: GPPointData>(
+    layer: &Layer<PD>,
+    (cidx, pidx, part): (usize, usize, PointPart),
+) -> Option<(f32, f32)> {
+    let point = layer.outline.get(cidx)?.iter().nth(pidx)?;
+    match part.which_handle() {
+        None => Some((point.x(), point.y())),
+        Some(wh) => match point.get_handle(wh)? {
+            GPHandle::At(x, y) => Some((x, y)),
+            GPHandle::Colocated => None,
+        },
+    }
 }
 
+/// The halo behind a hovered point or handle, in a layer under every marker.
+fn batch_hover_halo(batch: &mut PointBatch, viewport: &Viewport, at: (f32, f32)) -> f32 {
+    let radius = point_radius(viewport.factor) * HOVER_HALO_SCALE;
+    batch.set_layer(BatchLayer::Halo);
+    batch.fill(HOVER_HALO_FILL).add_circle(at, radius, None);
+    batch
+        .stroke(HOVER_HALO_STROKE, HOVER_HALO_THICKNESS * (1. / viewport.factor))
+        .add_circle(at, radius, None);
+    radius
+}
+
+/// Draws the points, handles and labels of the active layer. Markers are batched by paint, so a
+/// glyph with thousands of points takes a handful of draw calls; labels are drawn over them.
+/// Under `PreviewMode::NoUnselectedPoints` only selected points and handles are drawn.
+///
+/// `hovered`, if given, is the point or handle under the mouse, as returned by `hit::hit_test`; it
+/// gets a halo and its coordinates whether or not it's selected, as long as it's drawn.
 pub fn draw_all<PD: GPPointData>(
     glyph: &MFEKGlif<PD>,
     viewport: &Viewport,
@@ -333,66 +681,113 @@ pub fn draw_all<PD: GPPointData>(
     vcidx: Option<usize>,
     vpidx: Option<usize>,
     selected: &HashSet<(usize, usize)>,
+    selected_handles: &HashSet<(usize, usize, PointPart)>,
+    hovered: Option<(usize, usize, PointPart)>,
     canvas: &mut Canvas,
-    only_selected: bool,
 ) {
-    let mut i: isize = -1;
     let handle_style = viewport.handle_style;
-    let should_draw = |draw: bool| !only_selected || (only_selected && draw);
+    let should_draw = |selected: bool| is_shown(viewport, selected);
+    // A selected point's handles are drawn selected too, but handles may also be selected alone.
+    let handles_selected = |cidx: usize, pidx: usize, selected: bool| {
+        (
+            selected || selected_handles.contains(&(cidx, pidx, PointPart::HandleA)),
+            selected || selected_handles.contains(&(cidx, pidx, PointPart::HandleB)),
+        )
+    };
+    let is_selected = |cidx: usize, pidx: usize| {
+        selected.contains(&(cidx, pidx)) || (vcidx == Some(cidx) && vpidx == Some(pidx))
+    };
 
-    for (lidx, layer) in glyph.layers.iter().enumerate() {
-        if lidx != active_layer {
-            continue;
-        };
-        if handle_style == HandleStyle::Handlebars {
-            for (cidx, contour) in layer.outline.iter().enumerate() {
-
-                for (pidx, point) in contour.iter().enumerate() {
-                    let selected = if (lidx == active_layer && selected.contains(&(cidx, pidx)))
-                        || (lidx == active_layer && vcidx == Some(cidx) && vpidx == Some(pidx))
-                    {
-                        true
-                    } else {
-                        false
-                    };
-                    if should_draw(selected) {
-                        draw_handlebars(viewport, point, selected, canvas);
-                    }
-                }
-            }
-        }
+    let layer = match glyph.layers.get(active_layer) {
+        Some(layer) => layer,
+        None => return,
+    };
+    let mut batch = PointBatch::new();
 
-        for (cidx, contour) in layer.outline.iter().enumerate() {
-            if contour.is_open() {
-                i += 1;
-            }
+    // Hidden points and handles can't be hovered
+    let hovered = hovered.filter(|&(cidx, pidx, _)| {
+        let selected = is_selected(cidx, pidx);
+        let (a, b) = handles_selected(cidx, pidx, selected);
+        should_draw(selected || a || b)
+    });
+    let hovered = hovered.and_then(|h| {
+        hovered_position(layer, h).map(|at| (at, batch_hover_halo(&mut batch, viewport, at)))
+    });
 
+    // Bars are in a layer of their own, under every point and handle.
+    if handle_style != HandleStyle::None {
+        for (cidx, contour) in layer.outline.iter().enumerate() {
             for (pidx, point) in contour.iter().enumerate() {
-                if let Some(handle_b) = point.get_handle(WhichHandle::B) {
-                    if handle_b != GPHandle::Colocated {
-                        i += 1;
+                let selected = is_selected(cidx, pidx);
+                let (a, b) = handles_selected(cidx, pidx, selected);
+                if !should_draw(selected || a || b) {
+                    continue;
+                }
+                match handle_style {
+                    HandleStyle::Handlebars => {
+                        batch_handlebars(&mut batch, viewport, point, (a, b))
+                    }
+                    HandleStyle::Floating => {
+                        batch_handle_stubs(&mut batch, viewport, point, (a, b))
                     }
+                    HandleStyle::None => unreachable!(),
                 }
+            }
+        }
+    }
 
-                let selected = if (lidx == active_layer && selected.contains(&(cidx, pidx)))
-                    || (lidx == active_layer && vcidx == Some(cidx) && vpidx == Some(pidx))
-                {
-                    true
-                } else {
-                    false
+    // TrueType numbering converts every curve to quadratic, so only number when it's shown.
+    let numbering = viewport
+        .point_labels
+        .contains(&PointLabels::Numbered)
+        .then(|| numbering::number_points(layer, viewport.point_numbering));
+    let mut labels = vec![];
+    for (cidx, contour) in layer.outline.iter().enumerate() {
+        let points: Vec<_> = contour.iter().collect();
+        let tint = match viewport.contour_labels {
+            ContourLabels::IndicesAndColors => Some(names::contour_color(cidx)),
+            _ => None,
+        };
+        let spiro_types: Option<Vec<_>> = contour.spiro().map(|s| s.iter().map(|p| p.ty).collect());
+        let quadratic = quad::is_quadratic(&points);
+        for (pidx, point) in points.iter().copied().enumerate() {
+            let selected = is_selected(cidx, pidx);
+            let (mut a, b) = handles_selected(cidx, pidx, selected);
+            if quadratic && (pidx + 1 < points.len() || !contour.is_open()) {
+                // The off-curve point after this one is also the next point's handle B.
+                let next = (pidx + 1) % points.len();
+                a |= handles_selected(cidx, next, is_selected(cidx, next)).1;
+            }
+            if should_draw(selected || a || b) {
+                let state = PointState {
+                    kind: classify_in_contour(&points, pidx, contour.is_open()),
+                    selected,
+                    handles_selected: (a, b),
+                    tint,
                 };
-
-                if should_draw(selected) {
-                    draw_complete_point(viewport, point, Some(i), selected, canvas);
+                let number = numbering.as_ref().and_then(|n| n[cidx][pidx]).map(|i| i as isize);
+                match spiro_types.as_ref().and_then(|types| types.get(pidx)) {
+                    Some(ty) => spiro::batch_spiro_point(
+                        &mut batch, viewport, &points, pidx, contour.is_open(), *ty, state,
+                    ),
+                    None if quadratic => quad::batch_quad_point(&mut batch, viewport, point, state),
+                    None => batch_point(&mut batch, viewport, point, state),
                 }
-
-                if let Some(handle_b) = point.get_handle(WhichHandle::B) {
-                    if handle_b != GPHandle::Colocated {
-                        i += 1;
-                    }
-                }
-                i += 1;
+                labels.push((point, number, state));
             }
         }
     }
+    batch.draw(canvas);
+
+    for (point, number, state) in labels {
+        draw_point_labels(viewport, point, number, state, canvas);
+    }
+    for (cidx, contour) in layer.outline.iter().enumerate() {
+        if let Some(first) = contour.iter().next() {
+            names::draw_contour_index(viewport, (first.x(), first.y()), cidx, canvas);
+        }
+    }
+    if let Some((at, radius)) = hovered {
+        names::draw_point_location(viewport, (at.0 + radius, at.1 - radius), at, canvas);
+    }
 }
@@ -1,8 +1,9 @@
+use glifparser::PointType;
 use skia_safe::Canvas;
 
 use crate::constants::*;
-use crate::string::{UiString, AutoSizeMode};
-use crate::toggles::PreviewMode;
+use crate::string::{Alignment, AutoSizeMode, UiString};
+use crate::toggles::{ContourLabels, HandleLabels, PreviewMode};
 use crate::viewport::Viewport;
 
 pub fn draw_point_str(viewport: &Viewport, at: (f32, f32), s: &str, canvas: &mut Canvas) {
@@ -27,16 +28,90 @@ pub fn draw_point_name(viewport: &Viewport, at: (f32, f32), fill: u32, s: &str,
     uis.draw(viewport, at, canvas);
 }
 
+/// Draws each of `labels` above the one before it, the first at `at`.
+pub fn draw_point_strs<S: AsRef<str>>(
+    viewport: &Viewport,
+    at: (f32, f32),
+    labels: &[S],
+    canvas: &mut Canvas,
+) {
+    if viewport.preview_mode == PreviewMode::Paper {
+        return;
+    }
+    let mut y = at.1;
+    for s in labels {
+        let uis = UiString::new(s.as_ref()).padding(POINT_LABEL_PADDING);
+        uis.draw(viewport, (at.0, y), canvas);
+        y += uis.measure(viewport).1 * (1. / viewport.factor);
+    }
+}
+
 pub fn draw_point_number(viewport: &Viewport, at: (f32, f32), number: isize, canvas: &mut Canvas) {
     draw_point_str(viewport, at, &number.to_string(), canvas);
 }
 
+pub(crate) fn location_str(at: (f32, f32)) -> String {
+    format!("{}, {}", at.0 as i32, at.1 as i32)
+}
+
 pub fn draw_point_location(
     viewport: &Viewport,
     at: (f32, f32),
     original: (f32, f32),
     canvas: &mut Canvas,
 ) {
-    let s = format!("{}, {}", original.0 as i32, original.1 as i32);
-    draw_point_str(viewport, at, &s, canvas);
+    draw_point_str(viewport, at, &location_str(original), canvas);
+}
+
+/// The name a point type has in a .glif file.
+pub fn point_type_str(ptype: PointType) -> &'static str {
+    match ptype {
+        PointType::Move => "move",
+        PointType::Line => "line",
+        PointType::Curve => "curve",
+        PointType::QCurve => "qcurve",
+        PointType::OffCurve => "offcurve",
+        _ => "undefined",
+    }
+}
+
+/// Labels a handle with its length and/or angle, both relative to its on-curve point.
+pub fn draw_handle_label(
+    viewport: &Viewport,
+    point: (f32, f32),
+    handle: (f32, f32),
+    canvas: &mut Canvas,
+) {
+    let (dx, dy) = (handle.0 - point.0, handle.1 - point.1);
+    let length = f32::hypot(dx, dy);
+    let angle = f32::atan2(dy, dx).to_degrees();
+    let s = match viewport.handle_labels {
+        HandleLabels::None => return,
+        HandleLabels::Lengths => format!("{:.1}", length),
+        HandleLabels::Angles => format!("{:.1}°", angle),
+        HandleLabels::LengthsAndAngles => format!("{:.1}, {:.1}°", length, angle),
+    };
+    draw_point_str(viewport, handle, &s, canvas);
+}
+
+pub fn draw_contour_index(viewport: &Viewport, at: (f32, f32), index: usize, canvas: &mut Canvas) {
+    if viewport.preview_mode == PreviewMode::Paper {
+        return;
+    }
+    let color = match viewport.contour_labels {
+        ContourLabels::None => return,
+        ContourLabels::Indices => DEFAULT_STRING_COLOR,
+        ContourLabels::IndicesAndColors => contour_color(index),
+    };
+    let s = format!("#{}", index);
+    let uis = UiString::with_colors(&s, color, Some(DEFAULT_STRING_BGCOLOR))
+        .alignment(Alignment::Right)
+        .padding(POINT_LABEL_PADDING);
+    let offset = POINT_RADIUS * 3. * (1. / viewport.factor);
+    uis.draw(viewport, (at.0 - offset, at.1 + offset), canvas);
+}
+
+/// The color a contour is tinted with under `ContourLabels::IndicesAndColors`.
+pub fn contour_color(index: usize) -> u32 {
+    CONTOUR_PALETTE[index % CONTOUR_PALETTE.len()]
 }
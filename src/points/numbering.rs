@@ -0,0 +1,111 @@
+//! Point numbers as shown by `PointLabels::Numbered`, matching the indices other tools show.
+
+use glifparser::glif::contour::MFEKContourCommon;
+use glifparser::glif::point::MFEKPointCommon;
+use glifparser::glif::Layer;
+use glifparser::{Handle as GPHandle, PointData as GPPointData, WhichHandle};
+
+use super::quad::{is_implied, is_quadratic};
+use super::segments::cubic_segments;
+use crate::constants::TRUETYPE_CONVERSION_TOLERANCE;
+use crate::toggles::PointNumbering;
+
+fn has_handle<PD: GPPointData>(point: &dyn MFEKPointCommon<PD>, wh: WhichHandle) -> bool {
+    matches!(point.get_handle(wh), Some(GPHandle::At(_, _)))
+}
+
+/// Off-curve points between the on-curve points `from` and `to` of a contour, as in a .glif. A
+/// quadratic segment's one off-curve point is stored as both handles, so only `from`'s A counts.
+fn ufo_off_curves<PD: GPPointData>(
+    from: &dyn MFEKPointCommon<PD>,
+    to: &dyn MFEKPointCommon<PD>,
+    quadratic: bool,
+) -> usize {
+    if quadratic {
+        has_handle(from, WhichHandle::A) as usize
+    } else {
+        has_handle(from, WhichHandle::A) as usize + has_handle(to, WhichHandle::B) as usize
+    }
+}
+
+/// Numbers the points of a contour in the order they're stored in a .glif, from `counter` on.
+/// Implied points of a quadratic contour aren't stored, so they get no number.
+fn number_stored<PD: GPPointData>(
+    points: &[&dyn MFEKPointCommon<PD>],
+    open: bool,
+    counter: &mut usize,
+) -> Vec<Option<usize>> {
+    let quadratic = is_quadratic(points);
+    let mut numbers = Vec::with_capacity(points.len());
+    for (pidx, point) in points.iter().enumerate() {
+        if pidx > 0 {
+            *counter += ufo_off_curves(points[pidx - 1], *point, quadratic);
+        }
+        if quadratic && is_implied(*point) {
+            numbers.push(None);
+            continue;
+        }
+        numbers.push(Some(*counter));
+        *counter += 1;
+    }
+    // The closing segment's off-curve points come after the last point.
+    if !open && points.len() > 1 {
+        *counter += ufo_off_curves(points[points.len() - 1], points[0], quadratic);
+    }
+    numbers
+}
+
+/// Numbers every on-curve point of `layer`, indexed as `[contour][point]`. Points with no number
+/// in `mode` are `None`.
+///
+/// * `Ufo` counts every `<point>` of the .glif, off-curve points included, across all contours.
+///   A quadratic contour's implied on-curve points aren't counted, as they aren't stored.
+/// * `TrueType` counts the points of the `glyf` table, after conversion to quadratic curves with
+///   `TRUETYPE_CONVERSION_TOLERANCE`. Implied on-curve points aren't counted, as they aren't
+///   stored. Quadratic contours are counted as they are, like `Ufo` does.
+/// * `PerContour` restarts at zero for each contour and only counts on-curve points.
+pub fn number_points<PD: GPPointData>(
+    layer: &Layer<PD>,
+    mode: PointNumbering,
+) -> Vec<Vec<Option<usize>>> {
+    let mut counter = 0;
+    let mut ret = vec![];
+    for contour in layer.outline.iter() {
+        let points: Vec<_> = contour.iter().collect();
+        let numbers = match mode {
+            PointNumbering::PerContour => (0..points.len()).map(Some).collect(),
+            PointNumbering::Ufo => number_stored(&points, contour.is_open(), &mut counter),
+            // Already quadratic, so stored in the `glyf` table as in the .glif
+            PointNumbering::TrueType if is_quadratic(&points) => {
+                number_stored(&points, contour.is_open(), &mut counter)
+            }
+            PointNumbering::TrueType => {
+                let tolerance = TRUETYPE_CONVERSION_TOLERANCE as f64;
+                // TrueType contours are always closed, an open one's closing segment is a line
+                // without off-curve points, so there's no need to count it.
+                let segments = cubic_segments(contour);
+                let mut numbers = Vec::with_capacity(points.len());
+                for pidx in 0..points.len() {
+                    numbers.push(Some(counter));
+                    counter += 1;
+                    let bez = match segments.get(pidx) {
+                        Some(seg) => seg.bez,
+                        None => continue,
+                    };
+                    if bez.p1 == bez.p0 && bez.p2 == bez.p3 {
+                        continue; // a line
+                    }
+                    counter += bez
+                        .approx_spline(tolerance)
+                        .map(|spline| spline.points().len() - 2)
+                        // n quads are stored as n off-curve points with n - 1 on-curve points
+                        // between them.
+                        .unwrap_or_else(|| bez.to_quads(tolerance).count() * 2 - 1);
+                }
+                numbers
+            }
+        };
+        ret.push(numbers);
+    }
+    ret
+}
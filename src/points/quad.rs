@@ -0,0 +1,63 @@
+//! Quadratic (TrueType style) contours. glifparser stores the off-curve point of a quadratic
+//! segment twice, as the handle leading out of the on-curve point before it and as the handle
+//! leading into the one after it, and stores the on-curve points a .glif implies between two
+//! consecutive off-curve points like any other. `draw_all` draws each off-curve point once, as the
+//! handle A of the point before it, and implied points as hollow markers, so that what's drawn is
+//! the points a .glif would store.
+
+use glifparser::glif::point::MFEKPointCommon;
+use glifparser::{Handle as GPHandle, PointData as GPPointData, PointType, WhichHandle};
+
+use super::batch::{BatchLayer, PointBatch};
+use super::{
+    batch_handle, batch_marker, batch_round_point, get_point_stroke_fill, handle_radius,
+    PointState,
+};
+use crate::toggles::HandleStyle;
+use crate::viewport::Viewport;
+
+/// Whether a contour's segments are quadratic.
+pub fn is_quadratic<PD: GPPointData>(points: &[&dyn MFEKPointCommon<PD>]) -> bool {
+    points.iter().any(|p| p.get_point_type() == PointType::QCurve)
+}
+
+/// Whether an on-curve point of a quadratic contour is implied: halfway between its two handles,
+/// where a .glif wouldn't store it.
+pub fn is_implied<PD: GPPointData>(point: &dyn MFEKPointCommon<PD>) -> bool {
+    match (point.get_handle(WhichHandle::A), point.get_handle(WhichHandle::B)) {
+        (Some(GPHandle::At(ax, ay)), Some(GPHandle::At(bx, by))) => {
+            let (mx, my) = ((ax + bx) / 2., (ay + by) / 2.);
+            (point.x() - mx).abs() < 1e-3 && (point.y() - my).abs() < 1e-3
+        }
+        _ => false,
+    }
+}
+
+/// Batches a point of a quadratic contour. `state.handles_selected.0` is whether the off-curve
+/// point after it is selected, through either of the handles it's stored as.
+pub(crate) fn batch_quad_point<PD: GPPointData>(
+    batch: &mut PointBatch,
+    viewport: &Viewport,
+    point: &dyn MFEKPointCommon<PD>,
+    state: PointState,
+) {
+    if is_implied(point) {
+        let (_stroke, fill) = match state.tint {
+            Some(tint) if !state.selected => (tint, tint),
+            _ => get_point_stroke_fill(true, state.selected),
+        };
+        let at = (point.x(), point.y());
+        let factor = viewport.factor;
+        batch.set_layer(BatchLayer::Points.or_selected(state.selected));
+        batch_round_point(batch, at, handle_radius(factor), fill, 0.5, factor);
+    } else {
+        batch_marker(batch, viewport, point, state);
+    }
+
+    // Handle B is the same off-curve point as the previous point's handle A.
+    if viewport.handle_style != HandleStyle::None {
+        if let Some(a) = point.get_handle(WhichHandle::A) {
+            batch_handle(batch, viewport, a, state.handles_selected.0);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Splitting contours into their cubic Bézier segments, for the overlays that analyse the outline
+//! rather than just draw it.
+
+use glifparser::glif::contour::MFEKContourCommon;
+use glifparser::glif::point::MFEKPointCommon;
+use glifparser::glif::Layer;
+use glifparser::{Handle as GPHandle, PointData as GPPointData, WhichHandle};
+use kurbo::CubicBez;
+
+/// A segment between two on-curve points of a contour, by their point indices.
+pub(crate) struct Segment {
+    pub from: usize,
+    pub to: usize,
+    pub bez: CubicBez,
+}
+
+fn handle_or_point<PD: GPPointData>(
+    point: &dyn MFEKPointCommon<PD>,
+    wh: WhichHandle,
+) -> (f64, f64) {
+    match point.get_handle(wh) {
+        Some(GPHandle::At(x, y)) => (x as f64, y as f64),
+        _ => (point.x() as f64, point.y() as f64),
+    }
+}
+
+/// A contour's segments in order. Handle A leads out of a point and handle B into it; a colocated
+/// handle sits on its point.
+fn contour_segments<PD: GPPointData>(
+    points: &[&dyn MFEKPointCommon<PD>],
+    open: bool,
+) -> Vec<Segment> {
+    let len = points.len();
+    let count = if open { len.saturating_sub(1) } else { len };
+    if len < 2 {
+        return vec![];
+    }
+    (0..count)
+        .map(|from| {
+            let to = (from + 1) % len;
+            let (p, q) = (points[from], points[to]);
+            Segment {
+                from,
+                to,
+                bez: CubicBez::new(
+                    (p.x() as f64, p.y() as f64),
+                    handle_or_point(p, WhichHandle::A),
+                    handle_or_point(q, WhichHandle::B),
+                    (q.x() as f64, q.y() as f64),
+                ),
+            }
+        })
+        .collect()
+}
+
+/// A contour's segments once it's converted to cubic, as `points::contour_skia_path` draws it.
+/// Hyperbezier and Spiro points don't carry the handles that give their contour its shape, so
+/// their segments are between the points of the converted contour, not the contour's own.
+pub(crate) fn cubic_segments<PD: GPPointData>(
+    contour: &dyn MFEKContourCommon<PD>,
+) -> Vec<Segment> {
+    if contour.cubic().is_some() {
+        let points: Vec<_> = contour.iter().collect();
+        contour_segments(&points, contour.is_open())
+    } else {
+        let cubic = contour.to_cubic();
+        let points: Vec<_> = cubic.iter().collect();
+        contour_segments(&points, contour.is_open())
+    }
+}
+
+/// Every contour of `layer` as (contour index, whether open, segments).
+pub(crate) fn layer_segments<PD: GPPointData>(
+    layer: &Layer<PD>,
+) -> Vec<(usize, bool, Vec<Segment>)> {
+    layer
+        .outline
+        .iter()
+        .enumerate()
+        .map(|(cidx, contour)| (cidx, contour.is_open(), cubic_segments(contour)))
+        .collect()
+}
@@ -0,0 +1,96 @@
+//! Markers for the points of Spiro contours, which have a type of their own instead of handles.
+
+use glifparser::glif::point::spiro::SpiroPointType;
+use glifparser::glif::point::MFEKPointCommon;
+use glifparser::PointData as GPPointData;
+use skia_safe::{Point as SkPoint, Vector};
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use super::batch::{BatchLayer, PointBatch};
+use super::kind::neighbours;
+use super::{
+    batch_diamond_point, batch_round_point, batch_square_point, get_point_stroke_fill,
+    point_radius, PointState,
+};
+use crate::constants::*;
+use crate::viewport::Viewport;
+
+type Color = u32;
+
+/// A half disc around `at`, its flat side facing along `towards`.
+fn batch_half_disc_point(
+    batch: &mut PointBatch,
+    at: (f32, f32),
+    radius: f32,
+    towards: Vector,
+    stroke: Color,
+    factor: f32,
+) {
+    const STEPS: usize = 8;
+    let base = towards.y.atan2(towards.x);
+    let at = SkPoint::from(at);
+    let path = batch.stroke(stroke, DIRECTION_STROKE_THICKNESS * (1. / factor));
+    // The curved side goes round the back, away from `towards`.
+    for i in 0..=STEPS {
+        let theta = base + FRAC_PI_2 + PI * i as f32 / STEPS as f32;
+        let p = at + Vector::new(theta.cos(), theta.sin()) * radius;
+        if i == 0 {
+            path.move_to(p);
+        } else {
+            path.line_to(p);
+        }
+    }
+    path.close();
+}
+
+/// Whether a Spiro point of type `ty` is drawn as a square rather than a round marker.
+pub(crate) fn is_square(ty: SpiroPointType) -> bool {
+    !matches!(
+        ty,
+        SpiroPointType::G4 | SpiroPointType::G2 | SpiroPointType::Left | SpiroPointType::Right
+    )
+}
+
+/// Draws a Spiro point by its type: G4 as a circle, G2 as a diamond, corners (and open contour
+/// ends) as squares, and left and right constraints as half discs whose flat side faces the
+/// straight segment they join. `state.kind` isn't used, as the type decides the shape.
+pub(crate) fn batch_spiro_point<PD: GPPointData>(
+    batch: &mut PointBatch,
+    viewport: &Viewport,
+    points: &[&dyn MFEKPointCommon<PD>],
+    pidx: usize,
+    open: bool,
+    ty: SpiroPointType,
+    state: PointState,
+) {
+    let factor = viewport.factor;
+    let point = points[pidx];
+    let at = (point.x(), point.y());
+    let radius = point_radius(factor);
+    let round = !is_square(ty);
+    let (_stroke, fill) = match state.tint {
+        Some(tint) if !state.selected => (tint, tint),
+        _ => get_point_stroke_fill(round, state.selected),
+    };
+    let towards = |other: Option<&dyn MFEKPointCommon<PD>>| {
+        other
+            .map(|o| Vector::new(o.x() - at.0, o.y() - at.1))
+            .unwrap_or_else(|| Vector::new(1., 0.))
+    };
+    batch.set_layer(BatchLayer::Points.or_selected(state.selected));
+    let (prev, next) = neighbours(points, pidx, open);
+
+    match ty {
+        SpiroPointType::G4 => batch_round_point(batch, at, radius, fill, 1., factor),
+        SpiroPointType::G2 => batch_diamond_point(batch, at, radius, fill, factor),
+        // A left constraint joins a curve to the straight line after it
+        SpiroPointType::Left => {
+            batch_half_disc_point(batch, at, radius, towards(next), fill, factor)
+        }
+        // and a right constraint a straight line to the curve after it.
+        SpiroPointType::Right => {
+            batch_half_disc_point(batch, at, radius, towards(prev), fill, factor)
+        }
+        _ => batch_square_point(batch, at, radius * 1.25, fill, factor),
+    }
+}
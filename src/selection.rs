@@ -0,0 +1,283 @@
+//! Drawing of selection overlays, and finding the points they select.
+
+use glifparser::glif::contour::MFEKContourCommon;
+use glifparser::glif::point::MFEKPointCommon;
+use glifparser::glif::Layer;
+use glifparser::PointData;
+use skia_safe::{Canvas, Paint, PaintStyle, Path, PathFillType, Point, Rect};
+use std::collections::HashSet;
+
+use crate::constants::*;
+use crate::guidelines::LineStyle;
+use crate::viewport::Viewport;
+
+/// A rubber-band selection, in device-space (window) coordinates.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Marquee {
+    /// Two opposite corners, in any order.
+    Rect((f32, f32), (f32, f32)),
+    /// A closed polygon, as traced by the mouse.
+    Lasso(Vec<(f32, f32)>),
+}
+
+impl Marquee {
+    /// The marquee's outline in glyph coordinates, or None if the viewport can't be inverted.
+    fn to_path(&self, viewport: &Viewport) -> Option<Path> {
+        let inverse = viewport.as_device_matrix().invert()?;
+        let mut path = Path::new();
+        match self {
+            Marquee::Rect(a, b) => {
+                let corners = [(a.0, a.1), (b.0, a.1), (b.0, b.1), (a.0, b.1)];
+                let corners: Vec<Point> = corners.iter().map(|p| Point::from(*p)).collect();
+                path.add_poly(&corners, true);
+            }
+            Marquee::Lasso(points) => {
+                let points: Vec<Point> = points.iter().map(|p| Point::from(*p)).collect();
+                path.add_poly(&points, true);
+            }
+        }
+        path.set_fill_type(PathFillType::EvenOdd);
+        path.transform(&inverse);
+        Some(path)
+    }
+}
+
+pub fn draw_marquee(viewport: &Viewport, canvas: &mut Canvas, marquee: &Marquee) {
+    let path = match marquee.to_path(viewport) {
+        Some(path) => path,
+        None => return,
+    };
+
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    paint.set_style(PaintStyle::Fill);
+    paint.set_color(MARQUEE_FILL);
+    canvas.draw_path(&path, &paint);
+
+    paint.set_style(PaintStyle::Stroke);
+    paint.set_color(MARQUEE_STROKE);
+    paint.set_stroke_width(MARQUEE_STROKE_THICKNESS * (1. / viewport.factor));
+    LineStyle::dashed().apply(viewport, &mut paint);
+    canvas.draw_path(&path, &paint);
+}
+
+/// The on-curve points of `layer` inside `marquee`, as `(contour index, point index)`, ready to be
+/// passed to `points::draw_all` as its `selected` set.
+pub fn points_in_marquee<PD: PointData>(
+    layer: &Layer<PD>,
+    viewport: &Viewport,
+    marquee: &Marquee,
+) -> HashSet<(usize, usize)> {
+    let mut ret = HashSet::new();
+    let path = match marquee.to_path(viewport) {
+        Some(path) => path,
+        None => return ret,
+    };
+
+    for (cidx, contour) in layer.outline.iter().enumerate() {
+        for (pidx, point) in contour.iter().enumerate() {
+            if path.contains((point.x(), point.y())) {
+                ret.insert((cidx, pidx));
+            }
+        }
+    }
+
+    ret
+}
+
+/// A part of the bounding box gizmo drawn around a selection. Sides are as seen on screen, so
+/// `Top` is the side with the greatest y in glyph coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GizmoPart {
+    TopLeft,
+    Top,
+    TopRight,
+    Right,
+    BottomRight,
+    Bottom,
+    BottomLeft,
+    Left,
+    Rotate,
+    Pivot,
+    /// Inside the bounding box but on none of its handles.
+    Inside,
+}
+
+impl GizmoPart {
+    /// The eight scale handles, clockwise from the top left corner.
+    pub const SCALE_HANDLES: [GizmoPart; 8] = [
+        GizmoPart::TopLeft,
+        GizmoPart::Top,
+        GizmoPart::TopRight,
+        GizmoPart::Right,
+        GizmoPart::BottomRight,
+        GizmoPart::Bottom,
+        GizmoPart::BottomLeft,
+        GizmoPart::Left,
+    ];
+
+    /// Where a scale handle is along the bounding box, as fractions of its width and height from
+    /// its bottom left corner.
+    fn scale_fraction(&self) -> Option<(f32, f32)> {
+        Some(match self {
+            GizmoPart::TopLeft => (0., 1.),
+            GizmoPart::Top => (0.5, 1.),
+            GizmoPart::TopRight => (1., 1.),
+            GizmoPart::Right => (1., 0.5),
+            GizmoPart::BottomRight => (1., 0.),
+            GizmoPart::Bottom => (0.5, 0.),
+            GizmoPart::BottomLeft => (0., 0.),
+            GizmoPart::Left => (0., 0.5),
+            _ => return None,
+        })
+    }
+
+    /// The scale handle across the bounding box from this one, which stays put while this one is
+    /// dragged.
+    pub fn opposite(&self) -> Option<GizmoPart> {
+        let idx = GizmoPart::SCALE_HANDLES.iter().position(|p| p == self)?;
+        Some(GizmoPart::SCALE_HANDLES[(idx + 4) % 8])
+    }
+}
+
+/// The bounding box of a selection, with handles to scale it from any side or corner and to rotate
+/// it about its pivot.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SelectionGizmo {
+    /// In glyph coordinates.
+    pub bounds: Rect,
+    /// In glyph coordinates; the center of `bounds` unless moved.
+    pub pivot: (f32, f32),
+}
+
+impl SelectionGizmo {
+    /// The gizmo around the on-curve points of `layer` in `selected`, as passed to
+    /// `points::draw_all`, or None if none of them are in the layer.
+    pub fn new<PD: PointData>(
+        layer: &Layer<PD>,
+        selected: &HashSet<(usize, usize)>,
+    ) -> Option<Self> {
+        let mut points = layer.outline.iter().enumerate().flat_map(|(cidx, contour)| {
+            contour
+                .iter()
+                .enumerate()
+                .filter(move |(pidx, _)| selected.contains(&(cidx, *pidx)))
+                .map(|(_, point)| (point.x(), point.y()))
+                .collect::<Vec<_>>()
+        });
+        let first = points.next()?;
+        let (l, b, r, t) = points.fold((first.0, first.1, first.0, first.1), |(l, b, r, t), p| {
+            (l.min(p.0), b.min(p.1), r.max(p.0), t.max(p.1))
+        });
+        let bounds = Rect::from_ltrb(l, b, r, t);
+        Some(Self {
+            bounds,
+            pivot: (bounds.center_x(), bounds.center_y()),
+        })
+    }
+
+    pub fn with_pivot(mut self, pivot: (f32, f32)) -> Self {
+        self.pivot = pivot;
+        self
+    }
+
+    /// Where a scale handle is, in glyph coordinates.
+    pub fn scale_handle_position(&self, part: GizmoPart) -> Option<(f32, f32)> {
+        let (fx, fy) = part.scale_fraction()?;
+        Some((
+            self.bounds.left + self.bounds.width() * fx,
+            self.bounds.top + self.bounds.height() * fy,
+        ))
+    }
+
+    /// Where the rotation handle is, in glyph coordinates. It's a constant distance on screen above
+    /// the middle of the top side.
+    pub fn rotate_handle_position(&self, viewport: &Viewport) -> (f32, f32) {
+        (
+            self.bounds.center_x(),
+            self.bounds.bottom + GIZMO_ROTATE_OFFSET * (1. / viewport.factor),
+        )
+    }
+
+    pub fn draw(&self, viewport: &Viewport, canvas: &mut Canvas) {
+        let factor = viewport.factor;
+        let size = GIZMO_HANDLE_SIZE * (1. / factor);
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_stroke_width(GIZMO_STROKE_THICKNESS * (1. / factor));
+
+        // `bounds` is in glyph coordinates, so its "bottom" is the top on screen.
+        let top = (self.bounds.center_x(), self.bounds.bottom);
+        let rotate = self.rotate_handle_position(viewport);
+        let mut path = Path::new();
+        path.add_rect(self.bounds, None);
+        path.move_to(top);
+        path.line_to(rotate);
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_color(GIZMO_STROKE);
+        LineStyle::dashed().apply(viewport, &mut paint);
+        canvas.draw_path(&path, &paint);
+        paint.set_path_effect(None);
+
+        let mut handles = Path::new();
+        for part in GizmoPart::SCALE_HANDLES.iter() {
+            if let Some((x, y)) = self.scale_handle_position(*part) {
+                handles.add_rect(Rect::from_ltrb(x - size, y - size, x + size, y + size), None);
+            }
+        }
+        handles.add_circle(rotate, size, None);
+        for (style, color) in [
+            (PaintStyle::Fill, GIZMO_HANDLE_FILL),
+            (PaintStyle::Stroke, GIZMO_STROKE),
+        ] {
+            paint.set_style(style);
+            paint.set_color(color);
+            canvas.draw_path(&handles, &paint);
+        }
+
+        // The pivot is a circled cross.
+        let (px, py) = self.pivot;
+        let mut pivot = Path::new();
+        pivot.add_circle(self.pivot, size, None);
+        pivot.move_to((px - size * 2., py));
+        pivot.line_to((px + size * 2., py));
+        pivot.move_to((px, py - size * 2.));
+        pivot.line_to((px, py + size * 2.));
+        paint.set_style(PaintStyle::Stroke);
+        canvas.draw_path(&pivot, &paint);
+    }
+
+    /// The part of the gizmo under `position`, a device-space (window) coordinate. Handles are hit
+    /// a little outside of where they're drawn, and are checked before the inside of the box, so
+    /// they can still be grabbed when the box is too small on screen to show its inside.
+    pub fn hit_test(&self, viewport: &Viewport, position: (f32, f32)) -> Option<GizmoPart> {
+        let matrix = viewport.as_device_matrix();
+        let reach = GIZMO_HANDLE_SIZE * 1.5;
+        let hits = |at: (f32, f32)| {
+            let at = matrix.map_point(at);
+            (at.x - position.0).abs() <= reach && (at.y - position.1).abs() <= reach
+        };
+
+        if hits(self.rotate_handle_position(viewport)) {
+            return Some(GizmoPart::Rotate);
+        }
+        if hits(self.pivot) {
+            return Some(GizmoPart::Pivot);
+        }
+        for part in GizmoPart::SCALE_HANDLES.iter() {
+            if self.scale_handle_position(*part).map(hits).unwrap_or(false) {
+                return Some(*part);
+            }
+        }
+        let device_bounds = matrix.map_rect(self.bounds).0;
+        let (x, y) = position;
+        if x >= device_bounds.left
+            && x <= device_bounds.right
+            && y >= device_bounds.top
+            && y <= device_bounds.bottom
+        {
+            return Some(GizmoPart::Inside);
+        }
+        None
+    }
+}
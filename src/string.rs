@@ -184,16 +184,9 @@ impl UiString<'_> {
 }
 
 impl UiString<'_> {
-    pub fn draw(&self, viewport: &Viewport, at: (f32, f32), canvas: &mut Canvas) {
-        let matrix = canvas.local_to_device_as_3x3();
-        let at = matrix.map_point(at);
-        canvas.save();
-        canvas.set_matrix(&M44::new_identity());
+    fn font_size(&self, viewport: &Viewport) -> f32 {
         let factor = viewport.factor;
-        let mut paint = Paint::default();
-        paint.set_color(self.color);
-        paint.set_anti_alias(true);
-        let size = if self.autosized == AutoSizeMode::None {
+        if self.autosized == AutoSizeMode::None {
             self.size * factor
         } else if self.autosized == AutoSizeMode::OnlySmaller {
             if factor > 1. {
@@ -204,26 +197,46 @@ impl UiString<'_> {
         } else {
             // AutoSizeMode::Full
             self.size
-        };
+        }
+    }
 
-        let (blob, rect, metrics) = {
-            TEXTBLOBS.with(|b| {
-                let mut hm = b.borrow_mut();
-                if hm.capacity() > 10_000 {
-                    log::warn!("Text blob cache getting worryingly large (> 10k SkTextBlob's), culling it. This might be a bug, but is expected if you're generating many different strings w/Skia and can in that case be ignored.");
-                    hm.clear();
-                }
-                let b = hm.get(&(size.integer_decode(), self.string.to_string()));
-                match b {
-                    Some((bb, rr, mm)) => (bb.clone(), rr.clone(), mm.clone()),
-                    None => {
-                        let (bb, rr, mm) = self.make_textblob(size);
-                        hm.insert((size.integer_decode(), self.string.to_string()), (bb.clone(), rr.clone(), mm.clone()));
-                        (bb, rr, mm)
-                    }
+    fn cached_textblob(&self, size: f32) -> (TextBlob, Rect, Metrics) {
+        TEXTBLOBS.with(|b| {
+            let mut hm = b.borrow_mut();
+            if hm.capacity() > 10_000 {
+                log::warn!("Text blob cache getting worryingly large (> 10k SkTextBlob's), culling it. This might be a bug, but is expected if you're generating many different strings w/Skia and can in that case be ignored.");
+                hm.clear();
+            }
+            let b = hm.get(&(size.integer_decode(), self.string.to_string()));
+            match b {
+                Some((bb, rr, mm)) => (bb.clone(), rr.clone(), mm.clone()),
+                None => {
+                    let (bb, rr, mm) = self.make_textblob(size);
+                    hm.insert((size.integer_decode(), self.string.to_string()), (bb.clone(), rr.clone(), mm.clone()));
+                    (bb, rr, mm)
                 }
-            })
-        };
+            }
+        })
+    }
+
+    /// Size of this string's box on screen (width, height) in device pixels, padding included.
+    pub fn measure(&self, viewport: &Viewport) -> (f32, f32) {
+        let (_, rect, (_, line_spacing)) = self.cached_textblob(self.font_size(viewport));
+        let padding = self.padding.unwrap_or(0.);
+        (rect.width() + padding * 3., line_spacing + padding * 2.)
+    }
+
+    pub fn draw(&self, viewport: &Viewport, at: (f32, f32), canvas: &mut Canvas) {
+        let matrix = canvas.local_to_device_as_3x3();
+        let at = matrix.map_point(at);
+        canvas.save();
+        canvas.set_matrix(&M44::new_identity());
+        let mut paint = Paint::default();
+        paint.set_color(self.color);
+        paint.set_anti_alias(true);
+        let size = self.font_size(viewport);
+
+        let (blob, rect, metrics) = self.cached_textblob(size);
         let (metrics, line_spacing) = metrics;
 
         let padding = if let Some(padding) = self.padding {
@@ -8,11 +8,44 @@ pub enum HandleStyle {
     Floating,
 }
 
+/// What points are labeled with. `Viewport::point_labels` holds any number of these, whose labels
+/// are stacked above each point in that order; no labels is an empty list.
 #[derive(IntoEnumIterator, Display, Debug, Clone, Copy, PartialEq)]
 pub enum PointLabels {
-    None,
     Numbered,
     Locations,
+    /// The UFO point type: move, line, curve, qcurve or offcurve.
+    Types,
+    /// Marks points whose UFO smooth flag is set.
+    Smooth,
+    /// The UFO point identifier, for points that have one.
+    Identifiers,
+}
+
+/// Labels each contour with its index, optionally also tinting its outline and points with a
+/// color of its own.
+#[derive(IntoEnumIterator, Display, Debug, Clone, Copy, PartialEq)]
+pub enum ContourLabels {
+    None,
+    Indices,
+    IndicesAndColors,
+}
+
+/// Which order `PointLabels::Numbered` numbers points in.
+#[derive(IntoEnumIterator, Display, Debug, Clone, Copy, PartialEq)]
+pub enum PointNumbering {
+    Ufo,
+    TrueType,
+    PerContour,
+}
+
+/// Numeric feedback next to the handles of selected points.
+#[derive(IntoEnumIterator, Display, Debug, Clone, Copy, PartialEq)]
+pub enum HandleLabels {
+    None,
+    Lengths,
+    Angles,
+    LengthsAndAngles,
 }
 
 #[derive(IntoEnumIterator, Debug, Clone, Copy, PartialEq)]
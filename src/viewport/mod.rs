@@ -1,4 +1,6 @@
-use crate::toggles::{HandleStyle, PointLabels, PreviewMode};
+use crate::toggles::{
+    ContourLabels, HandleLabels, HandleStyle, PointLabels, PointNumbering, PreviewMode,
+};
 use skia_safe::{Canvas, Matrix};
 
 /// This structure represents the current viewport without requiring a reference to the Skia
@@ -10,7 +12,11 @@ pub struct Viewport {
     pub offset: (f32, f32),
     //pub dpi: f64,
     pub matrix: Matrix,
-    pub point_labels: PointLabels,
+    /// Each label at most once; see `with_point_labels`.
+    pub point_labels: Vec<PointLabels>,
+    pub point_numbering: PointNumbering,
+    pub handle_labels: HandleLabels,
+    pub contour_labels: ContourLabels,
     pub handle_style: HandleStyle,
     pub preview_mode: PreviewMode,
     /// We need a recalculated offset due to an unavoidable OS WM event that made our state
@@ -29,7 +35,10 @@ impl Default for Viewport {
             //dpi: 1.,
             broken: false,
             matrix: Matrix::new_identity(),
-            point_labels: PointLabels::None,
+            point_labels: vec![],
+            point_numbering: PointNumbering::Ufo,
+            handle_labels: HandleLabels::None,
+            contour_labels: ContourLabels::None,
             preview_mode: PreviewMode::None,
             handle_style: HandleStyle::Handlebars,
         }
@@ -54,8 +63,27 @@ impl Viewport {
         self.dpi = dpi;
         self
     }*/
-    pub fn with_point_labels(mut self, point_labels: PointLabels) -> Self {
-        self.point_labels = point_labels;
+    /// Labels are stacked in the order given. Repeats are dropped, as they'd stack the same label
+    /// twice.
+    pub fn with_point_labels(mut self, point_labels: &[PointLabels]) -> Self {
+        self.point_labels.clear();
+        for label in point_labels {
+            if !self.point_labels.contains(label) {
+                self.point_labels.push(*label);
+            }
+        }
+        self
+    }
+    pub fn with_point_numbering(mut self, point_numbering: PointNumbering) -> Self {
+        self.point_numbering = point_numbering;
+        self
+    }
+    pub fn with_handle_labels(mut self, handle_labels: HandleLabels) -> Self {
+        self.handle_labels = handle_labels;
+        self
+    }
+    pub fn with_contour_labels(mut self, contour_labels: ContourLabels) -> Self {
+        self.contour_labels = contour_labels;
         self
     }
     pub fn with_handle_style(mut self, handle_style: HandleStyle) -> Self {
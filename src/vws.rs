@@ -0,0 +1,192 @@
+//! Previews of MFEK's variable-width strokes (VWS): the stroked outline, and the ribs across the
+//! contour at each point whose ends set the stroke's width on either side.
+
+use glifparser::glif::contour::MFEKContourCommon;
+use glifparser::glif::contour_operations::vws::{VWSContour, VWSHandle};
+use glifparser::glif::contour_operations::ContourOperations;
+use glifparser::glif::Layer;
+use glifparser::PointData;
+use kurbo::{CubicBez, ParamCurve, ParamCurveDeriv, Vec2};
+use skia_safe::{Canvas, Paint, PaintStyle, Path, PathFillType, Point};
+use std::collections::HashSet;
+
+use crate::constants::*;
+use crate::points::segments::cubic_segments;
+use crate::points::{draw_round_point, get_handle_stroke_fill, handle_radius, is_shown};
+use crate::viewport::Viewport;
+
+/// The rib across a contour at one of its points, in glyph coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rib {
+    pub point: usize,
+    pub at: (f32, f32),
+    /// The end of the rib setting the stroke's width left of the contour's direction...
+    pub left: (f32, f32),
+    /// ...and the end setting it on the right.
+    pub right: (f32, f32),
+}
+
+fn vws_data<PD: PointData>(
+    operation: &Option<ContourOperations<PD>>,
+) -> Option<&VWSContour> {
+    match operation {
+        Some(ContourOperations::VariableWidthStroke { data }) => Some(data),
+        _ => None,
+    }
+}
+
+/// A contour's VWS parameters, if it has them and they can be previewed. `handles` has one entry
+/// per point of the contour, so only cubic contours are: converting a hyperbezier or Spiro contour
+/// to cubic changes its points, and its handles would land on the wrong ones.
+fn previewed_vws<'a, PD: PointData>(
+    contour: &'a dyn MFEKContourCommon<PD>,
+) -> Option<&'a VWSContour> {
+    contour.cubic()?;
+    vws_data(contour.operation())
+}
+
+/// Where the ends of a rib are, at `t` on `bez`, for the given offsets. The rib is perpendicular to
+/// the curve, then turned by `tangent_offset` radians. None where the curve has no tangent.
+fn rib_ends(
+    bez: &CubicBez,
+    t: f64,
+    (left, right, tangent_offset): (f64, f64, f64),
+) -> Option<(Vec2, Vec2, Vec2)> {
+    let at = bez.eval(t).to_vec2();
+    let tangent = bez.deriv().eval(t).to_vec2();
+    if tangent.hypot() < 1e-9 {
+        return None;
+    }
+    let normal = Vec2::new(-tangent.y, tangent.x).normalize();
+    let (sin, cos) = tangent_offset.sin_cos();
+    let normal = Vec2::new(
+        normal.x * cos - normal.y * sin,
+        normal.x * sin + normal.y * cos,
+    );
+    Some((at, at + normal * left, at - normal * right))
+}
+
+fn offsets(handle: &VWSHandle) -> (f64, f64, f64) {
+    (handle.left_offset, handle.right_offset, handle.tangent_offset)
+}
+
+/// The ribs of every cubic contour of `layer` that has VWS parameters, as (contour index, ribs).
+pub fn layer_ribs<PD: PointData>(layer: &Layer<PD>) -> Vec<(usize, Vec<Rib>)> {
+    let mut ret = vec![];
+    for (cidx, contour) in layer.outline.iter().enumerate() {
+        let vws = match previewed_vws(contour) {
+            Some(vws) => vws,
+            None => continue,
+        };
+        let segments = cubic_segments(contour);
+        let ribs = vws
+            .handles
+            .iter()
+            .enumerate()
+            .filter_map(|(pidx, handle)| {
+                // The tangent leaving the point, or entering it at the end of an open contour.
+                let (bez, t) = match segments.iter().find(|s| s.from == pidx) {
+                    Some(seg) => (seg.bez, 0.),
+                    None => (segments.iter().find(|s| s.to == pidx)?.bez, 1.),
+                };
+                let (at, left, right) = rib_ends(&bez, t, offsets(handle))?;
+                Some(Rib {
+                    point: pidx,
+                    at: (at.x as f32, at.y as f32),
+                    left: (left.x as f32, left.y as f32),
+                    right: (right.x as f32, right.y as f32),
+                })
+            })
+            .collect();
+        ret.push((cidx, ribs));
+    }
+    ret
+}
+
+/// The outline of every cubic contour of `layer` that has VWS parameters, approximately as it'll be
+/// stroked: each side is sampled `VWS_PREVIEW_SAMPLES` times per segment, its offsets interpolated
+/// linearly between the segment's ribs, and joins and caps are left flat. It's for previewing
+/// while the ribs are edited; the exported outline is built by MFEKmath.
+pub fn stroked_outline<PD: PointData>(layer: &Layer<PD>) -> Path {
+    let mut path = Path::new();
+    path.set_fill_type(PathFillType::EvenOdd);
+    for contour in layer.outline.iter() {
+        let vws = match previewed_vws(contour) {
+            Some(vws) => vws,
+            None => continue,
+        };
+        let (mut lefts, mut rights) = (vec![], vec![]);
+        for seg in cubic_segments(contour) {
+            let (from, to) = match (vws.handles.get(seg.from), vws.handles.get(seg.to)) {
+                (Some(from), Some(to)) => (offsets(from), offsets(to)),
+                _ => continue,
+            };
+            for i in 0..=VWS_PREVIEW_SAMPLES {
+                let t = i as f64 / VWS_PREVIEW_SAMPLES as f64;
+                let lerp = |a: f64, b: f64| a + (b - a) * t;
+                let offsets = (lerp(from.0, to.0), lerp(from.1, to.1), lerp(from.2, to.2));
+                if let Some((_, left, right)) = rib_ends(&seg.bez, t, offsets) {
+                    lefts.push(Point::new(left.x as f32, left.y as f32));
+                    rights.push(Point::new(right.x as f32, right.y as f32));
+                }
+            }
+        }
+        rights.reverse();
+        if contour.is_open() {
+            // Down one side and back up the other.
+            lefts.extend(rights);
+            path.add_poly(&lefts, true);
+        } else {
+            // Two rings, wound opposite ways.
+            path.add_poly(&lefts, true);
+            path.add_poly(&rights, true);
+        }
+    }
+    path
+}
+
+/// Draws the VWS preview of `layer`: the stroked outline, then each contour's ribs in `RIB_STROKE`
+/// with a handle at both ends. `selected` are the ribs being edited, as (contour index, point
+/// index); their handles are drawn selected, and under `PreviewMode::NoUnselectedPoints` they're
+/// the only ribs drawn.
+pub fn draw_vws<PD: PointData>(
+    viewport: &Viewport,
+    layer: &Layer<PD>,
+    selected: &HashSet<(usize, usize)>,
+    canvas: &mut Canvas,
+) {
+    let factor = viewport.factor;
+    let outline = stroked_outline(layer);
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    paint.set_style(PaintStyle::Fill);
+    paint.set_color(VWS_PREVIEW_FILL);
+    canvas.draw_path(&outline, &paint);
+    paint.set_style(PaintStyle::Stroke);
+    paint.set_color(OUTLINE_STROKE);
+    paint.set_stroke_width(OUTLINE_STROKE_THICKNESS * (1. / factor));
+    canvas.draw_path(&outline, &paint);
+
+    let ribs: Vec<_> = layer_ribs(layer)
+        .into_iter()
+        .flat_map(|(cidx, ribs)| ribs.into_iter().map(move |rib| (cidx, rib)))
+        .map(|(cidx, rib)| (rib, selected.contains(&(cidx, rib.point))))
+        .filter(|(_, selected)| is_shown(viewport, *selected))
+        .collect();
+
+    let mut bars = Path::new();
+    for (rib, _) in ribs.iter() {
+        bars.move_to(rib.left);
+        bars.line_to(rib.right);
+    }
+    paint.set_color(RIB_STROKE);
+    paint.set_stroke_width(HANDLEBAR_THICKNESS * (1. / factor));
+    canvas.draw_path(&bars, &paint);
+
+    for (rib, selected) in ribs {
+        let (_fill, stroke) = get_handle_stroke_fill(selected);
+        for end in [rib.left, rib.right] {
+            draw_round_point(end, handle_radius(factor), stroke, 0.5, canvas, factor);
+        }
+    }
+}